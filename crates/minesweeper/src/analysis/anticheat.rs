@@ -0,0 +1,285 @@
+use serde::Serialize;
+
+use crate::analysis::classifier::{classify, Classification};
+use crate::analysis::replay::{merge_moves, Move, MoveKind};
+use crate::parsers::parser::{Action, Generator, ParsedData};
+
+/// Survival chance below which a streak of lucky guesses gets reported
+const GUESS_SURVIVAL_THRESHOLD: f64 = 0.1;
+/// Minimum amount of guesses before the guess heuristics are considered
+const MIN_GUESSES: usize = 3;
+/// Minimum amount of zero time reactions before they are reported
+const MIN_ZERO_TIME_MOVES: usize = 5;
+/// Ratio of zero time reactions to all moves at which the heuristic reaches full severity
+const ZERO_TIME_FULL_RATIO: f64 = 0.5;
+/// Minimum amount of placed flags before the flag heuristic is considered
+const MIN_FLAGS: usize = 10;
+/// Median time in milliseconds between a flag and the move before it, below which flagging is considered inhuman
+const INHUMAN_FLAG_INTERVAL_MS: i64 = 150;
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum Heuristic {
+    /// Never hitting a mine although the player was forced to guess a lot
+    ForcedGuessLuck,
+    /// Clicks in the same tick as the previous move, on a different cell
+    ZeroTimeReactions,
+    /// Opening cells that could not be deduced while safe cells were available
+    UndeducibleOpens,
+    /// Every flag on a mine while flagging faster than humanly possible
+    FlagAccuracySpeed,
+}
+
+impl Heuristic {
+    fn weight(&self) -> f64 {
+        match self {
+            Heuristic::ForcedGuessLuck => 40_f64,
+            Heuristic::ZeroTimeReactions => 20_f64,
+            Heuristic::UndeducibleOpens => 25_f64,
+            Heuristic::FlagAccuracySpeed => 15_f64,
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CheatReport {
+    /// Suspicion score between 0 (nothing found) and 100
+    pub score: u32,
+    pub first_click: FirstClick,
+    pub findings: Vec<Finding>,
+}
+
+/// Outcome of the first click, judged by the guarantees of the board generator.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum FirstClick {
+    /// The game does not contain any open
+    NotPlayed,
+    Safe,
+    /// The first click hit a mine, which the generator allows
+    Mine,
+    /// The first click contradicts the generator, e.g. a mine on a first click safe board
+    GeneratorBug,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Finding {
+    pub heuristic: Heuristic,
+    /// How strongly the heuristic triggered, between 0 and 1
+    pub severity: f64,
+    pub description: String,
+    pub evidence: Vec<EvidenceMove>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EvidenceMove {
+    /// Position of the move in the merged action timeline
+    pub index: usize,
+    pub x: usize,
+    pub y: usize,
+    pub total_time: i64,
+    pub action: &'static str,
+}
+
+impl EvidenceMove {
+    fn new(index: usize, mv: &Move) -> Self {
+        EvidenceMove {
+            index,
+            x: mv.x,
+            y: mv.y,
+            total_time: mv.total_time,
+            action: match mv.kind {
+                MoveKind::Open => "open",
+                MoveKind::Flag(_) => "flag",
+                MoveKind::Chord => "chord",
+            },
+        }
+    }
+}
+
+/// Tracks guesses which the player survived, together with the odds of surviving all of them.
+struct GuessStreak {
+    survival: f64,
+    hit_mine: bool,
+    evidence: Vec<EvidenceMove>,
+}
+
+impl GuessStreak {
+    fn new() -> Self {
+        GuessStreak {
+            survival: 1_f64,
+            hit_mine: false,
+            evidence: Vec::new(),
+        }
+    }
+
+    fn record(&mut self, index: usize, mv: &Move, chance: f32, mine: bool) {
+        self.hit_mine |= mine;
+        self.survival *= 1_f64 - chance as f64;
+        self.evidence.push(EvidenceMove::new(index, mv));
+    }
+
+    fn into_finding(self, heuristic: Heuristic, description: &str) -> Option<Finding> {
+        if self.hit_mine
+            || self.evidence.len() < MIN_GUESSES
+            || self.survival >= GUESS_SURVIVAL_THRESHOLD
+        {
+            return None;
+        }
+
+        // One order of magnitude below the threshold counts as fully suspicious
+        let severity = ((GUESS_SURVIVAL_THRESHOLD / self.survival).log10()).clamp(0_f64, 1_f64);
+
+        Some(Finding {
+            heuristic,
+            severity,
+            description: format!(
+                "{} {} guesses survived with a combined chance of {:.4}%",
+                description,
+                self.evidence.len(),
+                self.survival * 100_f64
+            ),
+            evidence: self.evidence,
+        })
+    }
+}
+
+pub fn analyse(parsed_data: &ParsedData) -> CheatReport {
+    let moves = merge_moves(
+        &parsed_data.open_data,
+        &parsed_data.flag_data,
+        &parsed_data.chord_data,
+    );
+    let classified = classify(&parsed_data.game_board, &moves);
+    let timeunits = parsed_data.metadata.timeunits as i64;
+
+    let mut forced_guesses = GuessStreak::new();
+    let mut unnecessary_guesses = GuessStreak::new();
+    let mut zero_time_moves = Vec::new();
+    let mut flags = Vec::new();
+    let mut flag_intervals = Vec::new();
+    let generator = parsed_data.metadata.generator.as_ref();
+    let mut first_click = FirstClick::NotPlayed;
+
+    for (mv, classified_move) in moves.iter().zip(classified.iter()) {
+        let index = classified_move.index;
+        if index > 0 {
+            let previous = &moves[index - 1];
+            if previous.total_time == mv.total_time && (previous.x, previous.y) != (mv.x, mv.y) {
+                zero_time_moves.push(EvidenceMove::new(index, mv));
+            }
+        }
+
+        let chance = classified_move.mine_chance.unwrap_or(0_f32);
+        match (&mv.kind, classified_move.classification) {
+            (_, Classification::NoEffect) => {}
+            (MoveKind::Open, _) if first_click == FirstClick::NotPlayed => {
+                let field = &parsed_data.game_board.fields[mv.y][mv.x];
+                let protected = generator.is_some_and(|generator| generator.safe_first_click());
+                first_click = match generator {
+                    _ if field.mine && protected => FirstClick::GeneratorBug,
+                    _ if field.mine => FirstClick::Mine,
+                    Some(Generator::FirstClickOpening) if field.value != 0 => {
+                        FirstClick::GeneratorBug
+                    }
+                    _ => FirstClick::Safe,
+                };
+
+                // Only a known random generator makes the first click a real guess,
+                // otherwise it is assumed to be protected
+                if generator == Some(&Generator::Random) {
+                    forced_guesses.record(index, mv, chance, classified_move.mine);
+                }
+            }
+            (MoveKind::Open | MoveKind::Chord, Classification::Guess) => {
+                forced_guesses.record(index, mv, chance, classified_move.mine)
+            }
+            (MoveKind::Open | MoveKind::Chord, Classification::UnnecessaryRisk) => {
+                unnecessary_guesses.record(index, mv, chance, classified_move.mine)
+            }
+            (MoveKind::Flag(Action::Place), _) => {
+                flags.push((EvidenceMove::new(index, mv), classified_move.mine));
+                if index > 0 {
+                    flag_intervals.push((mv.total_time - moves[index - 1].total_time) * timeunits);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut findings: Vec<Finding> = [
+        forced_guesses.into_finding(
+            Heuristic::ForcedGuessLuck,
+            "Without any safe cell available",
+        ),
+        unnecessary_guesses.into_finding(
+            Heuristic::UndeducibleOpens,
+            "While deducible safe cells were available",
+        ),
+        zero_time_finding(zero_time_moves, moves.len()),
+        flag_finding(flags, flag_intervals),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    findings.sort_by(|a, b| b.severity.total_cmp(&a.severity));
+
+    let score = findings
+        .iter()
+        .map(|finding| finding.severity * finding.heuristic.weight())
+        .sum::<f64>()
+        .round()
+        .min(100_f64) as u32;
+
+    CheatReport {
+        score,
+        first_click,
+        findings,
+    }
+}
+
+fn zero_time_finding(evidence: Vec<EvidenceMove>, total_moves: usize) -> Option<Finding> {
+    if evidence.len() < MIN_ZERO_TIME_MOVES {
+        return None;
+    }
+
+    let ratio = evidence.len() as f64 / total_moves as f64;
+
+    Some(Finding {
+        heuristic: Heuristic::ZeroTimeReactions,
+        severity: (ratio / ZERO_TIME_FULL_RATIO).min(1_f64),
+        description: format!(
+            "{} of {} moves happened in the same tick as the previous move",
+            evidence.len(),
+            total_moves
+        ),
+        evidence,
+    })
+}
+
+fn flag_finding(flags: Vec<(EvidenceMove, bool)>, mut intervals: Vec<i64>) -> Option<Finding> {
+    if flags.len() < MIN_FLAGS || intervals.is_empty() || flags.iter().any(|(_, mine)| !mine) {
+        return None;
+    }
+
+    intervals.sort_unstable();
+    let median = intervals[intervals.len() / 2];
+    if median >= INHUMAN_FLAG_INTERVAL_MS {
+        return None;
+    }
+
+    Some(Finding {
+        heuristic: Heuristic::FlagAccuracySpeed,
+        severity: 1_f64 - median as f64 / INHUMAN_FLAG_INTERVAL_MS as f64,
+        description: format!(
+            "All {} flags were correct with a median reaction time of {}ms",
+            flags.len(),
+            median
+        ),
+        evidence: flags.into_iter().map(|(evidence, _)| evidence).collect(),
+    })
+}
@@ -0,0 +1,156 @@
+use image::Rgba;
+use serde::Serialize;
+
+use crate::analysis::replay::{Move, MoveKind};
+use crate::analysis::solver::{CellKnowledge, Solver};
+use crate::minesweeper_logic::{Board, FieldState};
+use crate::parsers::parser::Action;
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum Classification {
+    /// The opened cell was logically proven to be safe
+    DeducibleSafe,
+    /// No safe cell could be deduced anywhere, so the player had to guess
+    Guess,
+    /// The player guessed although a deducible safe cell was available
+    UnnecessaryRisk,
+    /// The opened cell was logically proven to be a mine
+    Mistake,
+    /// The open targeted a cell that was not closed, e.g. a number or a flag
+    NoEffect,
+    /// A flag got placed on a mine or removed from a cell without one
+    Correct,
+    /// A flag got placed on a cell without a mine or removed from a mine
+    Incorrect,
+}
+
+impl Classification {
+    pub fn colour(&self) -> Option<Rgba<u8>> {
+        match self {
+            Classification::DeducibleSafe => Some(Rgba([46, 204, 64, 110])),
+            Classification::Guess => Some(Rgba([255, 220, 0, 130])),
+            Classification::UnnecessaryRisk => Some(Rgba([255, 133, 27, 140])),
+            Classification::Mistake => Some(Rgba([255, 65, 54, 150])),
+            Classification::NoEffect => None,
+            Classification::Correct => Some(Rgba([0, 116, 217, 110])),
+            Classification::Incorrect => Some(Rgba([240, 18, 190, 150])),
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ClassifiedMove {
+    /// Position of the move in the merged action timeline
+    pub index: usize,
+    pub x: usize,
+    pub y: usize,
+    pub total_time: i64,
+    pub action: &'static str,
+    pub classification: Classification,
+    /// Whether the targeted cell, or for chords any of the opened cells, actually contained a mine
+    pub mine: bool,
+    /// Chance of hitting a mine based on the visible board, only set for opens and chords
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mine_chance: Option<f32>,
+}
+
+/// Replays the moves on a copy of the board and labels every single one of them
+/// using what was visible right before the move happened.
+pub fn classify(board: &Board, moves: &[Move]) -> Vec<ClassifiedMove> {
+    let mut board = board.clone();
+    let mut solver = Solver::default();
+    let mut lost = false;
+
+    moves
+        .iter()
+        .enumerate()
+        .map(|(index, mv)| {
+            let field = &board.fields[mv.y][mv.x];
+
+            let (action, classification, mine, mine_chance) = match &mv.kind {
+                MoveKind::Open if lost || field.field_state != FieldState::Closed => {
+                    ("open", Classification::NoEffect, field.mine, None)
+                }
+                MoveKind::Open => {
+                    let (classification, chance) =
+                        classify_opens(&mut solver, &board, &[(mv.x, mv.y)]);
+                    ("open", classification, field.mine, Some(chance))
+                }
+                MoveKind::Chord => {
+                    let targets = board.chord_targets(mv.x, mv.y);
+                    let mine = targets.iter().any(|(x, y)| board.fields[*y][*x].mine);
+                    if lost || targets.is_empty() {
+                        ("chord", Classification::NoEffect, mine, None)
+                    } else {
+                        let (classification, chance) =
+                            classify_opens(&mut solver, &board, &targets);
+                        ("chord", classification, mine, Some(chance))
+                    }
+                }
+                MoveKind::Flag(action) => {
+                    let correct = match action {
+                        Action::Remove => !field.mine,
+                        Action::Place | Action::Toggle => field.mine,
+                    };
+                    let classification = if correct {
+                        Classification::Correct
+                    } else {
+                        Classification::Incorrect
+                    };
+                    ("flag", classification, field.mine, None)
+                }
+            };
+
+            if !lost {
+                mv.apply(&mut board);
+                lost = mine && action != "flag" && classification != Classification::NoEffect;
+            }
+
+            ClassifiedMove {
+                index,
+                x: mv.x,
+                y: mv.y,
+                total_time: mv.total_time,
+                action,
+                classification,
+                mine,
+                mine_chance,
+            }
+        })
+        .collect()
+}
+
+/// Classifies opening the given cells at once, together with the chance that at least one of them is a mine.
+fn classify_opens(
+    solver: &mut Solver,
+    board: &Board,
+    cells: &[(usize, usize)],
+) -> (Classification, f32) {
+    let knowledge = solver.solve(board);
+
+    let classification = if cells
+        .iter()
+        .any(|(x, y)| knowledge.at(*x, *y) == CellKnowledge::Mine)
+    {
+        Classification::Mistake
+    } else if cells
+        .iter()
+        .all(|(x, y)| knowledge.at(*x, *y) == CellKnowledge::Safe)
+    {
+        Classification::DeducibleSafe
+    } else if knowledge.has_safe_cell() {
+        Classification::UnnecessaryRisk
+    } else {
+        Classification::Guess
+    };
+
+    // Treats the cells as independent, which is exact for a single cell
+    let survival: f32 = cells
+        .iter()
+        .map(|(x, y)| 1_f32 - knowledge.chance_at(*x, *y))
+        .product();
+
+    (classification, 1_f32 - survival)
+}
@@ -0,0 +1,6 @@
+pub mod anticheat;
+pub mod classifier;
+pub mod overlay;
+pub mod probability;
+pub mod replay;
+pub mod solver;
@@ -0,0 +1,57 @@
+use image::Rgba;
+
+use crate::analysis::classifier::{classify, Classification};
+use crate::analysis::probability::chance_colour;
+use crate::analysis::replay::merge_moves;
+use crate::parsers::parser::ParsedData;
+
+/// Colour tint per cell, indexed by `[y][x]`, drawn on top of the textures.
+pub type Overlay = Vec<Vec<Option<Rgba<u8>>>>;
+
+#[derive(Copy, Clone, Debug)]
+pub enum OverlayType {
+    Solver,
+    Probability,
+}
+
+impl std::str::FromStr for OverlayType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_ref() {
+            "solver" => Ok(OverlayType::Solver),
+            "probability" => Ok(OverlayType::Probability),
+            _ => Err(format!("Unknown overlay type: {}", s)),
+        }
+    }
+}
+
+pub fn build_overlay(overlay_type: OverlayType, parsed_data: &ParsedData) -> Overlay {
+    let mut overlay = vec![
+        vec![None; parsed_data.metadata.x_size as usize];
+        parsed_data.metadata.y_size as usize
+    ];
+
+    let moves = merge_moves(
+        &parsed_data.open_data,
+        &parsed_data.flag_data,
+        &parsed_data.chord_data,
+    );
+
+    // Later moves on the same cell win, so a removed flag shows the removal
+    for classified in classify(&parsed_data.game_board, &moves) {
+        let colour = match overlay_type {
+            OverlayType::Solver => classified.classification.colour(),
+            OverlayType::Probability => classified
+                .mine_chance
+                .filter(|_| classified.classification != Classification::NoEffect)
+                .map(chance_colour),
+        };
+
+        if let Some(colour) = colour {
+            overlay[classified.y][classified.x] = Some(colour);
+        }
+    }
+
+    overlay
+}
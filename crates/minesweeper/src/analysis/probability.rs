@@ -0,0 +1,261 @@
+use image::Rgba;
+use serde::Serialize;
+
+use crate::analysis::replay::Move;
+use crate::analysis::solver::{solve, CellKnowledge, Tally};
+use crate::minesweeper_logic::{Board, FieldState};
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ProbabilityMap {
+    /// Last tick whose moves are part of the board state
+    pub tick: i64,
+    /// Chance of every cell being a mine indexed by `[y][x]`, open cells are `null`
+    pub cells: Vec<Vec<Option<f32>>>,
+}
+
+/// Computes the mine chance of every closed cell as it was visible after all moves up to the given tick.
+/// Without a tick the final board state is used.
+pub fn probability_map(board: &Board, moves: &[Move], tick: Option<i64>) -> ProbabilityMap {
+    let tick = tick.unwrap_or_else(|| moves.last().map(|mv| mv.total_time).unwrap_or(0));
+
+    let mut board = board.clone();
+    moves
+        .iter()
+        .take_while(|mv| mv.total_time <= tick)
+        .for_each(|mv| mv.apply(&mut board));
+
+    let knowledge = solve(&board);
+
+    let cells = board
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .map(|(x, field)| {
+                    (field.field_state != FieldState::Open).then(|| knowledge.chance_at(x, y))
+                })
+                .collect()
+        })
+        .collect();
+
+    ProbabilityMap { tick, cells }
+}
+
+/// Fades from green for a safe cell to red for a certain mine.
+pub fn chance_colour(chance: f32) -> Rgba<u8> {
+    let chance = chance.clamp(0_f32, 1_f32);
+    let mix = |safe: u8, mine: u8| (safe as f32 + (mine as f32 - safe as f32) * chance) as u8;
+
+    Rgba([mix(46, 255), mix(204, 65), mix(64, 54), 140])
+}
+
+pub(crate) struct Chance {
+    pub(crate) value: f64,
+    /// `Some(true)` if the cell is a mine in every possible configuration, `Some(false)` if in none
+    certain: Option<bool>,
+}
+
+impl Chance {
+    pub(crate) fn knowledge(&self) -> CellKnowledge {
+        match self.certain {
+            Some(true) => CellKnowledge::Mine,
+            Some(false) => CellKnowledge::Safe,
+            None => CellKnowledge::Unknown,
+        }
+    }
+}
+
+pub(crate) struct Weighted {
+    /// Chance per cell of every component, in the order of the given tallies
+    pub(crate) components: Vec<Vec<Chance>>,
+    /// Chance of each cell that is not next to any number
+    pub(crate) pool: Chance,
+}
+
+/// Combines the independent component solutions into exact chances.
+///
+/// A configuration using `k` mines on the frontier leaves `mines - k` for the pool, which can be
+/// placed in `C(pool, mines - k)` ways, so every configuration is weighted by that amount.
+/// Returns `None` if no combination is consistent with the total amount of mines.
+pub(crate) fn weigh(tallies: &[&Tally], pool: usize, mines: usize) -> Option<Weighted> {
+    let mut ln_factorial = vec![0_f64; pool + 1];
+    for n in 1..=pool {
+        ln_factorial[n] = ln_factorial[n - 1] + (n as f64).ln();
+    }
+    let ln_binomial = |m: usize| {
+        (m <= pool).then(|| ln_factorial[pool] - ln_factorial[m] - ln_factorial[pool - m])
+    };
+
+    // Scaling a component by a constant does not change any chance but keeps the products in range
+    let scales: Vec<f64> = tallies
+        .iter()
+        .map(|tally| {
+            tally
+                .by_mines
+                .iter()
+                .map(|by_mines| by_mines.solutions)
+                .fold(0_f64, f64::max)
+        })
+        .collect();
+    let distributions: Vec<Vec<f64>> = tallies
+        .iter()
+        .zip(&scales)
+        .map(|(tally, scale)| {
+            tally
+                .by_mines
+                .iter()
+                .map(|by_mines| by_mines.solutions / scale)
+                .collect()
+        })
+        .collect();
+
+    let mut prefix = vec![vec![1_f64]];
+    for distribution in &distributions {
+        prefix.push(convolve(prefix.last().unwrap(), distribution));
+    }
+    let mut suffix = vec![vec![1_f64]; distributions.len() + 1];
+    for i in (0..distributions.len()).rev() {
+        suffix[i] = convolve(&distributions[i], &suffix[i + 1]);
+    }
+    let total = &prefix[distributions.len()];
+
+    let max_ln = (0..total.len())
+        .filter(|k| total[*k] > 0_f64 && *k <= mines)
+        .filter_map(|k| ln_binomial(mines - k))
+        .fold(f64::NEG_INFINITY, f64::max);
+    if max_ln == f64::NEG_INFINITY {
+        return None;
+    }
+    let pool_weight = |frontier_mines: usize| {
+        mines
+            .checked_sub(frontier_mines)
+            .and_then(ln_binomial)
+            .map(|ln| (ln - max_ln).exp())
+            .unwrap_or(0_f64)
+    };
+
+    let normaliser: f64 = (0..total.len()).map(|k| total[k] * pool_weight(k)).sum();
+    if normaliser == 0_f64 {
+        return None;
+    }
+
+    let components = tallies
+        .iter()
+        .enumerate()
+        .map(|(i, tally)| {
+            let others = convolve(&prefix[i], &suffix[i + 1]);
+            let weights: Vec<f64> = (0..tally.by_mines.len())
+                .map(|k| {
+                    (0..others.len())
+                        .map(|rest| others[rest] * pool_weight(k + rest))
+                        .sum::<f64>()
+                        / scales[i]
+                })
+                .collect();
+
+            (0..tally.by_mines.first().map_or(0, |t| t.mine_hits.len()))
+                .map(|cell| {
+                    let mut mine = 0_f64;
+                    let mut free = 0_f64;
+                    for (by_mines, weight) in tally.by_mines.iter().zip(&weights) {
+                        mine += by_mines.mine_hits[cell] * weight;
+                        free += (by_mines.solutions - by_mines.mine_hits[cell]) * weight;
+                    }
+                    Chance {
+                        value: mine / normaliser,
+                        certain: certainty(mine, free),
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    let pool = if pool == 0 {
+        Chance {
+            value: 0_f64,
+            certain: None,
+        }
+    } else {
+        let feasible: Vec<usize> = (0..total.len())
+            .filter(|k| total[*k] * pool_weight(*k) > 0_f64)
+            .collect();
+        let expected: f64 = feasible
+            .iter()
+            .map(|k| total[*k] * pool_weight(*k) * (mines - k) as f64)
+            .sum::<f64>()
+            / normaliser;
+
+        Chance {
+            value: expected / pool as f64,
+            certain: if feasible.iter().all(|k| mines - k == 0) {
+                Some(false)
+            } else if feasible.iter().all(|k| mines - k == pool) {
+                Some(true)
+            } else {
+                None
+            },
+        }
+    };
+
+    Some(Weighted { components, pool })
+}
+
+/// Fallback for boards where the total amount of mines does not add up, e.g. due to corrupted data.
+/// Every component is looked at on its own and the pool gets the average of the remaining mines.
+pub(crate) fn unweighted(tallies: &[&Tally], pool: usize, mines: usize) -> Weighted {
+    let mut expected_frontier_mines = 0_f64;
+
+    let components = tallies
+        .iter()
+        .map(|tally| {
+            let solutions: f64 = tally.by_mines.iter().map(|t| t.solutions).sum();
+            (0..tally.by_mines.first().map_or(0, |t| t.mine_hits.len()))
+                .map(|cell| {
+                    let mine: f64 = tally.by_mines.iter().map(|t| t.mine_hits[cell]).sum();
+                    expected_frontier_mines += mine / solutions;
+                    Chance {
+                        value: mine / solutions,
+                        certain: certainty(mine, solutions - mine),
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    let density = if pool == 0 {
+        0_f64
+    } else {
+        ((mines as f64 - expected_frontier_mines).max(0_f64) / pool as f64).min(1_f64)
+    };
+
+    Weighted {
+        components,
+        pool: Chance {
+            value: density,
+            certain: None,
+        },
+    }
+}
+
+fn certainty(mine: f64, free: f64) -> Option<bool> {
+    if mine == 0_f64 {
+        Some(false)
+    } else if free == 0_f64 {
+        Some(true)
+    } else {
+        None
+    }
+}
+
+fn convolve(a: &[f64], b: &[f64]) -> Vec<f64> {
+    let mut result = vec![0_f64; a.len() + b.len() - 1];
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            result[i + j] += x * y;
+        }
+    }
+    result
+}
@@ -0,0 +1,92 @@
+use crate::minesweeper_logic::{Board, FieldState};
+use crate::parsers::parser::{Action, ChordAction, FlagAction, OpenAction};
+
+#[derive(Debug, Clone)]
+pub enum MoveKind {
+    Open,
+    Flag(Action),
+    Chord,
+}
+
+/// A single player action from either the open, the flag or the chord stream.
+#[derive(Debug, Clone)]
+pub struct Move {
+    pub x: usize,
+    pub y: usize,
+    pub total_time: i64,
+    pub kind: MoveKind,
+}
+
+impl Move {
+    pub(crate) fn apply(&self, board: &mut Board) {
+        match &self.kind {
+            MoveKind::Open => board.open_field(self.x, self.y),
+            MoveKind::Chord => board.chord_field(self.x, self.y),
+            MoveKind::Flag(action) => FlagAction {
+                x: self.x as i32,
+                y: self.y as i32,
+                time: 0,
+                action: action.clone(),
+                total_time: self.total_time,
+            }
+            .perform_action(board),
+        }
+    }
+}
+
+/// Merges all action streams into a single timeline.
+/// Within a tick flags come first, then opens and then chords, the same way the renderer applies them.
+pub fn merge_moves(
+    open_data: &[OpenAction],
+    flag_data: &[FlagAction],
+    chord_data: &[ChordAction],
+) -> Vec<Move> {
+    let mut moves: Vec<Move> = flag_data
+        .iter()
+        .map(|flag| Move {
+            x: flag.x as usize,
+            y: flag.y as usize,
+            total_time: flag.total_time,
+            kind: MoveKind::Flag(flag.action.clone()),
+        })
+        .chain(open_data.iter().map(|open| Move {
+            x: open.x as usize,
+            y: open.y as usize,
+            total_time: open.total_time,
+            kind: MoveKind::Open,
+        }))
+        .chain(chord_data.iter().map(|chord| Move {
+            x: chord.x as usize,
+            y: chord.y as usize,
+            total_time: chord.total_time,
+            kind: MoveKind::Chord,
+        }))
+        .collect();
+
+    // Stable sort keeps the original order of actions within the same stream and tick
+    moves.sort_by_key(|mv| mv.total_time);
+
+    moves
+}
+
+/// Finds the open or chord that hit a mine and ended the game, if there is one.
+pub fn losing_move<'a>(board: &Board, moves: &'a [Move]) -> Option<&'a Move> {
+    let mut board = board.clone();
+
+    moves.iter().find(|mv| {
+        let hit_mine = match mv.kind {
+            MoveKind::Open => {
+                let field = &board.fields[mv.y][mv.x];
+                field.mine && field.field_state == FieldState::Closed
+            }
+            MoveKind::Chord => board
+                .chord_targets(mv.x, mv.y)
+                .iter()
+                .any(|(x, y)| board.fields[*y][*x].mine),
+            MoveKind::Flag(_) => false,
+        };
+        mv.apply(&mut board);
+
+        hit_mine
+    })
+}
@@ -0,0 +1,417 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::analysis::probability::{unweighted, weigh};
+use crate::minesweeper_logic::{Board, FieldState};
+
+/// Upper bound of search nodes per frontier component, to keep a single request within the CPU limit.
+/// Components exceeding it are treated as undetermined.
+const MAX_SEARCH_NODES: usize = 250_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CellKnowledge {
+    /// The cell is open and shows its number
+    Revealed,
+    /// The cell is closed but can not be a mine in any configuration
+    Safe,
+    /// The cell is closed and has to be a mine in every configuration
+    Mine,
+    Unknown,
+}
+
+/// Everything a player could have known about the board from the visible numbers alone.
+/// Flags placed by the player are not trusted and treated as closed cells.
+pub struct Knowledge {
+    pub cells: Vec<Vec<CellKnowledge>>,
+    pub mine_chance: Vec<Vec<f32>>,
+}
+
+impl Knowledge {
+    pub fn at(&self, x: usize, y: usize) -> CellKnowledge {
+        self.cells[y][x]
+    }
+
+    pub fn chance_at(&self, x: usize, y: usize) -> f32 {
+        self.mine_chance[y][x]
+    }
+
+    pub fn has_safe_cell(&self) -> bool {
+        self.cells
+            .iter()
+            .any(|row| row.contains(&CellKnowledge::Safe))
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct Constraint {
+    vars: Vec<usize>,
+    mines: usize,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct Component {
+    cells: Vec<(usize, usize)>,
+    constraints: Vec<Constraint>,
+}
+
+/// Solutions of a single component, grouped by the amount of mines they use.
+#[derive(Clone)]
+pub(crate) struct Tally {
+    pub(crate) by_mines: Vec<MineTally>,
+}
+
+#[derive(Clone)]
+pub(crate) struct MineTally {
+    pub(crate) solutions: f64,
+    pub(crate) mine_hits: Vec<f64>,
+}
+
+/// Solves a single board, use a [Solver] to solve the states of a replay one after another.
+pub fn solve(board: &Board) -> Knowledge {
+    Solver::default().solve(board)
+}
+
+/// Solves successive states of the same game. A move only changes the frontier around the cells
+/// it opened, so every component that looks exactly like before reuses its earlier search.
+#[derive(Default)]
+pub struct Solver {
+    /// Search results of the components of the last solved board, `None` if the budget ran out
+    tallies: HashMap<Component, Option<Tally>>,
+}
+
+impl Solver {
+    pub fn solve(&mut self, board: &Board) -> Knowledge {
+        solve_with(board, &mut self.tallies)
+    }
+}
+
+fn solve_with(board: &Board, cache: &mut HashMap<Component, Option<Tally>>) -> Knowledge {
+    let width = board.metadata.x_size as usize;
+    let height = board.metadata.y_size as usize;
+
+    let mut cells = vec![vec![CellKnowledge::Unknown; width]; height];
+    let mut mine_chance = vec![vec![0_f32; width]; height];
+
+    for (y, row) in board.fields.iter().enumerate().take(height) {
+        for (x, field) in row.iter().enumerate().take(width) {
+            if field.field_state == FieldState::Open {
+                cells[y][x] = CellKnowledge::Revealed;
+            }
+        }
+    }
+
+    let mut frontier_cells: Vec<(usize, usize)> = Vec::new();
+    let mut frontier_index: Vec<Vec<Option<usize>>> = vec![vec![None; width]; height];
+    let mut constraints: Vec<Constraint> = Vec::new();
+
+    for y in 0..height {
+        for x in 0..width {
+            let field = &board.fields[y][x];
+            if cells[y][x] != CellKnowledge::Revealed || field.mine {
+                continue;
+            }
+
+            let vars: Vec<usize> = board
+                .neighbours(x, y)
+                .into_iter()
+                .filter(|(nx, ny)| cells[*ny][*nx] != CellKnowledge::Revealed)
+                .map(|(nx, ny)| {
+                    *frontier_index[ny][nx].get_or_insert_with(|| {
+                        frontier_cells.push((nx, ny));
+                        frontier_cells.len() - 1
+                    })
+                })
+                .collect();
+
+            if !vars.is_empty() {
+                constraints.push(Constraint {
+                    vars,
+                    mines: field.value as usize,
+                });
+            }
+        }
+    }
+
+    // Flags are not trusted, only mines that blew up are known for sure
+    let revealed_mines = (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .filter(|(x, y)| cells[*y][*x] == CellKnowledge::Revealed && board.fields[*y][*x].mine)
+        .count();
+    let mut remaining_mines = (board.mine_count as usize).saturating_sub(revealed_mines);
+
+    let mut pool: Vec<(usize, usize)> = (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .filter(|(x, y)| {
+            cells[*y][*x] == CellKnowledge::Unknown && frontier_index[*y][*x].is_none()
+        })
+        .collect();
+
+    let mut solved = Vec::new();
+    let mut tallies = HashMap::new();
+    for component in split_components(&frontier_cells, constraints) {
+        let tally = cache
+            .remove(&component)
+            .unwrap_or_else(|| enumerate(&component));
+        match &tally {
+            Some(found) => solved.push((component.clone(), found.clone())),
+            None => {
+                // Too large to search, fall back to the simple counting rules
+                // and let the remaining cells share the mines with the cells without information
+                for ((x, y), known) in component.cells.iter().zip(propagate(&component)) {
+                    match known {
+                        Some(true) => {
+                            cells[*y][*x] = CellKnowledge::Mine;
+                            remaining_mines = remaining_mines.saturating_sub(1);
+                        }
+                        Some(false) => cells[*y][*x] = CellKnowledge::Safe,
+                        None => pool.push((*x, *y)),
+                    }
+                }
+            }
+        }
+        tallies.insert(component, tally);
+    }
+    // Only the current components can show up again after the next move
+    *cache = tallies;
+
+    let tallies: Vec<&Tally> = solved.iter().map(|(_, tally)| tally).collect();
+    let weighted = weigh(&tallies, pool.len(), remaining_mines)
+        .unwrap_or_else(|| unweighted(&tallies, pool.len(), remaining_mines));
+
+    for ((component, _), chances) in solved.iter().zip(weighted.components) {
+        for ((x, y), chance) in component.cells.iter().zip(chances) {
+            cells[*y][*x] = chance.knowledge();
+            mine_chance[*y][*x] = chance.value as f32;
+        }
+    }
+
+    for (x, y) in pool {
+        cells[y][x] = weighted.pool.knowledge();
+        mine_chance[y][x] = weighted.pool.value as f32;
+    }
+
+    for y in 0..height {
+        for x in 0..width {
+            match cells[y][x] {
+                CellKnowledge::Safe => mine_chance[y][x] = 0_f32,
+                CellKnowledge::Mine => mine_chance[y][x] = 1_f32,
+                _ => {}
+            }
+        }
+    }
+
+    Knowledge { cells, mine_chance }
+}
+
+/// Groups frontier cells that are linked through shared constraints, since those can be solved independently.
+fn split_components(cells: &[(usize, usize)], constraints: Vec<Constraint>) -> Vec<Component> {
+    let mut var_constraints: Vec<Vec<usize>> = vec![Vec::new(); cells.len()];
+    for (i, constraint) in constraints.iter().enumerate() {
+        for var in &constraint.vars {
+            var_constraints[*var].push(i);
+        }
+    }
+
+    let mut var_seen = vec![false; cells.len()];
+    let mut constraint_seen = vec![false; constraints.len()];
+    let mut components = Vec::new();
+
+    for start in 0..cells.len() {
+        if var_seen[start] {
+            continue;
+        }
+
+        // Breadth first order keeps neighbouring cells close together which helps pruning
+        let mut order = Vec::new();
+        let mut member_constraints = Vec::new();
+        let mut queue = VecDeque::from([start]);
+        var_seen[start] = true;
+
+        while let Some(var) = queue.pop_front() {
+            order.push(var);
+            for constraint in &var_constraints[var] {
+                if constraint_seen[*constraint] {
+                    continue;
+                }
+                constraint_seen[*constraint] = true;
+                member_constraints.push(*constraint);
+                for next in &constraints[*constraint].vars {
+                    if !var_seen[*next] {
+                        var_seen[*next] = true;
+                        queue.push_back(*next);
+                    }
+                }
+            }
+        }
+
+        let mut local = vec![usize::MAX; cells.len()];
+        for (i, var) in order.iter().enumerate() {
+            local[*var] = i;
+        }
+
+        components.push(Component {
+            cells: order.iter().map(|var| cells[*var]).collect(),
+            constraints: member_constraints
+                .iter()
+                .map(|c| Constraint {
+                    vars: constraints[*c].vars.iter().map(|v| local[*v]).collect(),
+                    mines: constraints[*c].mines,
+                })
+                .collect(),
+        });
+    }
+
+    components
+}
+
+/// Applies the basic counting rules until nothing changes anymore.
+/// `Some(true)` is a known mine, `Some(false)` a known safe cell.
+fn propagate(component: &Component) -> Vec<Option<bool>> {
+    let mut known: Vec<Option<bool>> = vec![None; component.cells.len()];
+    let mut changed = true;
+
+    while changed {
+        changed = false;
+        for constraint in &component.constraints {
+            let mines = constraint
+                .vars
+                .iter()
+                .filter(|v| known[**v] == Some(true))
+                .count();
+            let unknown: Vec<usize> = constraint
+                .vars
+                .iter()
+                .copied()
+                .filter(|v| known[*v].is_none())
+                .collect();
+
+            if unknown.is_empty() || mines > constraint.mines {
+                continue;
+            }
+
+            let remaining = constraint.mines - mines;
+            if remaining == 0 || remaining == unknown.len() {
+                for var in unknown {
+                    known[var] = Some(remaining != 0);
+                }
+                changed = true;
+            }
+        }
+    }
+
+    known
+}
+
+struct Search<'a> {
+    component: &'a Component,
+    var_constraints: Vec<Vec<usize>>,
+    assignment: Vec<bool>,
+    placed: Vec<usize>,
+    open_slots: Vec<usize>,
+    mines: usize,
+    nodes: usize,
+    tally: Tally,
+}
+
+fn enumerate(component: &Component) -> Option<Tally> {
+    let size = component.cells.len();
+    let mut var_constraints = vec![Vec::new(); size];
+    for (i, constraint) in component.constraints.iter().enumerate() {
+        for var in &constraint.vars {
+            var_constraints[*var].push(i);
+        }
+    }
+
+    let mut search = Search {
+        component,
+        var_constraints,
+        assignment: vec![false; size],
+        placed: vec![0; component.constraints.len()],
+        open_slots: component.constraints.iter().map(|c| c.vars.len()).collect(),
+        mines: 0,
+        nodes: 0,
+        tally: Tally {
+            by_mines: Vec::new(),
+        },
+    };
+
+    if !search.step(0) || search.tally.by_mines.is_empty() {
+        return None;
+    }
+
+    Some(search.tally)
+}
+
+impl Search<'_> {
+    /// Returns false once the node budget is used up.
+    fn step(&mut self, var: usize) -> bool {
+        self.nodes += 1;
+        if self.nodes > MAX_SEARCH_NODES {
+            return false;
+        }
+
+        if var == self.assignment.len() {
+            let size = self.assignment.len();
+            if self.tally.by_mines.len() <= self.mines {
+                self.tally.by_mines.resize(
+                    self.mines + 1,
+                    MineTally {
+                        solutions: 0_f64,
+                        mine_hits: vec![0_f64; size],
+                    },
+                );
+            }
+
+            let tally = &mut self.tally.by_mines[self.mines];
+            tally.solutions += 1_f64;
+            for (i, mine) in self.assignment.iter().enumerate() {
+                if *mine {
+                    tally.mine_hits[i] += 1_f64;
+                }
+            }
+            return true;
+        }
+
+        for mine in [false, true] {
+            if self.assign(var, mine) && !self.step(var + 1) {
+                return false;
+            }
+            self.unassign(var, mine);
+        }
+
+        true
+    }
+
+    /// Assigns a value and reports whether every touched constraint can still be satisfied.
+    fn assign(&mut self, var: usize, mine: bool) -> bool {
+        self.assignment[var] = mine;
+        if mine {
+            self.mines += 1;
+        }
+        let mut feasible = true;
+        for constraint in &self.var_constraints[var] {
+            self.open_slots[*constraint] -= 1;
+            if mine {
+                self.placed[*constraint] += 1;
+            }
+            let target = self.component.constraints[*constraint].mines;
+            let placed = self.placed[*constraint];
+            if placed > target || placed + self.open_slots[*constraint] < target {
+                feasible = false;
+            }
+        }
+        feasible
+    }
+
+    fn unassign(&mut self, var: usize, mine: bool) {
+        self.assignment[var] = false;
+        if mine {
+            self.mines -= 1;
+        }
+        for constraint in &self.var_constraints[var] {
+            self.open_slots[*constraint] += 1;
+            if mine {
+                self.placed[*constraint] -= 1;
+            }
+        }
+    }
+}
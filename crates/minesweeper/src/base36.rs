@@ -0,0 +1,14 @@
+const BASE: i64 = 36;
+const CHARACTERS: &str = "0123456789abcdefghijklmnopqrstuvwxyz";
+
+/// Decodes the number case insensitively, fails on other characters and numbers too large for an i64.
+pub fn decode(number: &str) -> Option<i64> {
+    if number.is_empty() {
+        return None;
+    }
+
+    number.chars().try_fold(0i64, |result, character| {
+        let digit = CHARACTERS.find(character.to_ascii_lowercase())? as i64;
+        result.checked_mul(BASE)?.checked_add(digit)
+    })
+}
@@ -0,0 +1,11 @@
+const BASE: i64 = 62;
+const CHARACTERS: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// Decodes the number, fails on other characters and numbers too large for an i64.
+/// An empty number is zero.
+pub fn decode(number: &str) -> Option<i64> {
+    number.chars().try_fold(0i64, |result, character| {
+        let digit = CHARACTERS.find(character)? as i64;
+        result.checked_mul(BASE)?.checked_add(digit)
+    })
+}
@@ -0,0 +1,239 @@
+use std::time::Duration;
+
+use image::{GenericImage, ImageBuffer, Rgba};
+
+use crate::error::MinesweeperError;
+use crate::font::{characters_fitting, draw_text, GLYPH_HEIGHT};
+use crate::renderer::{
+    AnimationFrame, FrameSource, ReplayFrames, MAX_GIF_DIMENSION, MAX_IMAGE_DIMENSION,
+};
+
+/// Most games that can be replayed at once
+pub const MAX_PLAYERS: usize = 8;
+/// Space between two boards
+const GAP: u32 = 8;
+const HUD_SCALE: u32 = 2;
+const HUD_PADDING: u32 = 4;
+/// Two lines of text above every board
+const HUD_HEIGHT: u32 = 2 * GLYPH_HEIGHT * HUD_SCALE + 3 * HUD_PADDING;
+const BACKGROUND: Rgba<u8> = Rgba([32, 32, 32, 255]);
+const TEXT: Rgba<u8> = Rgba([240, 240, 240, 255]);
+const WON: Rgba<u8> = Rgba([103, 149, 60, 255]);
+const LOST: Rgba<u8> = Rgba([220, 40, 40, 255]);
+
+struct Player {
+    label: String,
+    frames: ReplayFrames,
+    /// Milliseconds on the shared clock at which the last move got replayed
+    finished_at: Option<i64>,
+}
+
+impl Player {
+    /// Milliseconds on the shared clock until the next move of the player
+    fn next_event(&self) -> Option<i64> {
+        self.frames
+            .next_tick()
+            .map(|tick| tick * self.frames.timeunits())
+    }
+
+    /// Finish time of a won game
+    fn won_at(&self) -> Option<i64> {
+        self.finished_at
+            .filter(|_| !self.frames.lost() && self.frames.done_percentage() == 100)
+    }
+
+    /// Second HUD line, `winners` holding the finish times of every player that won so far
+    fn status(&self, clock: i64, winners: &[i64]) -> (String, Rgba<u8>) {
+        let elapsed = self.finished_at.unwrap_or(clock);
+        let progress = format!(
+            "{:.2}S {}%",
+            elapsed as f64 / 1000_f64,
+            self.frames.done_percentage()
+        );
+
+        if let Some(won_at) = self.won_at() {
+            // Players finishing in the same moment share their place
+            let place = 1 + winners.iter().filter(|other| **other < won_at).count();
+            return (format!("{} #{}", progress, place), WON);
+        }
+
+        match self.finished_at {
+            None => (progress, TEXT),
+            Some(_) if self.frames.lost() => (format!("{} LOST", progress), LOST),
+            Some(_) => (format!("{} END", progress), TEXT),
+        }
+    }
+}
+
+/// Replays several games in a grid on a shared clock, so every frame shows all boards at the same
+/// moment of their games. Won games show their place in the finish order.
+pub struct Comparison {
+    players: Vec<Player>,
+    /// Milliseconds since the start of the games, `None` before the first frame
+    clock: Option<i64>,
+}
+
+impl Comparison {
+    /// Takes a label, shown above the board, together with the frames of every game.
+    /// Fails with [MinesweeperError::UnsupportedBoardSize] if the grid is larger than a still image may be.
+    pub fn new(players: Vec<(String, ReplayFrames)>) -> Result<Comparison, MinesweeperError> {
+        let comparison = Comparison {
+            players: players
+                .into_iter()
+                .map(|(label, frames)| Player {
+                    label,
+                    frames,
+                    finished_at: None,
+                })
+                .collect(),
+            clock: None,
+        };
+
+        let (width, height) = comparison.dimensions();
+        if width.max(height) > MAX_IMAGE_DIMENSION {
+            return Err(MinesweeperError::UnsupportedBoardSize);
+        }
+
+        Ok(comparison)
+    }
+
+    /// Longest side a single board should have for the grid to be about as large as a single gif.
+    /// Cells are never smaller than the smallest tile size, so large boards still grow the grid
+    /// beyond that, to about 2500 pixels for [MAX_PLAYERS] boards of the largest size.
+    pub fn max_board_dimension(players: usize) -> u32 {
+        MAX_GIF_DIMENSION / columns(players)
+    }
+
+    fn next_event(&self) -> Option<i64> {
+        self.players
+            .iter()
+            .filter_map(|player| player.next_event())
+            .min()
+    }
+
+    /// Size of a grid cell below the HUD, large enough for every board
+    fn board_dimensions(&self) -> (u32, u32) {
+        self.players
+            .iter()
+            .map(|player| player.frames.dimensions())
+            .fold((0, 0), |(width, height), (w, h)| {
+                (width.max(w), height.max(h))
+            })
+    }
+
+    fn render(
+        &mut self,
+        first: bool,
+        clock: i64,
+    ) -> Result<ImageBuffer<Rgba<u8>, Vec<u8>>, MinesweeperError> {
+        let (width, height) = self.dimensions();
+        let (board_width, board_height) = self.board_dimensions();
+        let columns = columns(self.players.len());
+        let winners: Vec<i64> = self
+            .players
+            .iter()
+            .filter_map(|player| player.won_at())
+            .collect();
+
+        let mut imgbuf = ImageBuffer::new(width, height);
+        if first {
+            imgbuf.pixels_mut().for_each(|pixel| *pixel = BACKGROUND);
+        }
+
+        let max_characters =
+            characters_fitting(board_width.saturating_sub(2 * HUD_PADDING), HUD_SCALE);
+
+        for (index, player) in self.players.iter_mut().enumerate() {
+            let left = (index as u32 % columns) * (board_width + GAP);
+            let top = (index as u32 / columns) * (HUD_HEIGHT + board_height + GAP);
+
+            for x in left..left + board_width {
+                for y in top..top + HUD_HEIGHT {
+                    imgbuf.put_pixel(x, y, BACKGROUND);
+                }
+            }
+
+            let label: String = player.label.chars().take(max_characters).collect();
+            draw_text(
+                &mut imgbuf,
+                left + HUD_PADDING,
+                top + HUD_PADDING,
+                &label,
+                HUD_SCALE,
+                TEXT,
+            );
+
+            let (status, colour) = player.status(clock, &winners);
+            draw_text(
+                &mut imgbuf,
+                left + HUD_PADDING,
+                top + 2 * HUD_PADDING + GLYPH_HEIGHT * HUD_SCALE,
+                &status,
+                HUD_SCALE,
+                colour,
+            );
+
+            imgbuf
+                .copy_from(&player.frames.render()?, left, top + HUD_HEIGHT)
+                .map_err(|_| MinesweeperError::ImageInsertion)?;
+        }
+
+        Ok(imgbuf)
+    }
+}
+
+impl FrameSource for Comparison {
+    fn dimensions(&self) -> (u32, u32) {
+        let (board_width, board_height) = self.board_dimensions();
+        let columns = columns(self.players.len());
+        let rows = (self.players.len() as u32).div_ceil(columns);
+
+        (
+            columns * board_width + columns.saturating_sub(1) * GAP,
+            rows * (HUD_HEIGHT + board_height) + rows.saturating_sub(1) * GAP,
+        )
+    }
+
+    fn colours(&self) -> Vec<Rgba<u8>> {
+        self.players
+            .iter()
+            .flat_map(|player| player.frames.colours())
+            .chain([BACKGROUND, TEXT, WON, LOST])
+            .collect()
+    }
+
+    fn next_frame(&mut self) -> Option<Result<AnimationFrame, MinesweeperError>> {
+        let first = self.clock.is_none();
+        let clock = if first { 0 } else { self.next_event()? };
+
+        if !first {
+            for player in self.players.iter_mut() {
+                while player.next_event().is_some_and(|event| event <= clock) {
+                    player.frames.apply_next_tick();
+                }
+            }
+        }
+        for player in self.players.iter_mut() {
+            if player.finished_at.is_none() && player.next_event().is_none() {
+                player.finished_at = Some(clock);
+            }
+        }
+        self.clock = Some(clock);
+
+        let delay = match self.next_event() {
+            _ if first => Duration::from_secs(1),
+            Some(next) => Duration::from_millis((next - clock) as u64),
+            None => Duration::from_secs(15),
+        };
+
+        Some(
+            self.render(first, clock)
+                .map(|image| AnimationFrame { image, delay }),
+        )
+    }
+}
+
+/// Columns of the most square grid holding all players
+fn columns(players: usize) -> u32 {
+    ((players as f64).sqrt().ceil() as u32).max(1)
+}
@@ -0,0 +1,52 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum MinesweeperError {
+    #[error("Gif Encoding Error")]
+    GifEncoding,
+    #[error("Png Encoding Error")]
+    PngEncoding,
+    #[error("Video Encoding Error")]
+    VideoEncoding,
+    #[error("Image insertion Error")]
+    ImageInsertion,
+    #[error("Unable to parse API Data")]
+    ApiDataParse,
+    #[error("The request to the provider failed")]
+    UpstreamRequest,
+    #[error("The provider did not answer in time")]
+    UpstreamTimeout,
+    #[error("The provider does not know this game")]
+    UpstreamNotFound,
+    #[error("The provider is rate limiting requests")]
+    RateLimited {
+        /// Seconds until the provider accepts requests again, if it told
+        retry_after: Option<u64>,
+    },
+    #[error("The provider rejected the credentials")]
+    UpstreamAuth,
+    #[error("The provider failed with status {0}")]
+    UpstreamServer(u16),
+    #[error("The provider answered with status {0}")]
+    UpstreamStatus(u16),
+    #[error("The provider does not support this")]
+    Unsupported,
+    #[error("The game id {0} is invalid")]
+    InvalidGameId(String),
+    #[error("Gamedata not found")]
+    GameDataNotFound,
+    #[error("Data could not be parsed")]
+    DataParseError,
+    #[error("The provider is missing the {0} setting")]
+    MissingConfig(&'static str),
+    #[error("The {0} setting of the provider is invalid")]
+    InvalidConfig(&'static str),
+    #[error("The Game Data is from an unsupported version.")]
+    UnsupportedVersion,
+    #[error("Image could not be rendered.")]
+    ImageRender,
+    #[error("The data seems to be corrupted.")]
+    DataParse,
+    #[error("The board size is not supported.")]
+    UnsupportedBoardSize,
+}
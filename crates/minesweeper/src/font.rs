@@ -0,0 +1,97 @@
+use image::{ImageBuffer, Rgba};
+
+/// Width of a glyph in font pixels
+const GLYPH_WIDTH: u32 = 3;
+/// Height of a glyph in font pixels
+pub(crate) const GLYPH_HEIGHT: u32 = 5;
+/// Space between two glyphs in font pixels
+const GLYPH_SPACING: u32 = 1;
+
+/// Tiny 3x5 bitmap font, every row holds three bits with the leftmost pixel being the highest bit.
+/// Lowercase letters are drawn as uppercase ones.
+fn glyph(character: char) -> [u8; 5] {
+    match character.to_ascii_uppercase() {
+        '0' => [7, 5, 5, 5, 7],
+        '1' => [2, 6, 2, 2, 7],
+        '2' => [7, 1, 7, 4, 7],
+        '3' => [7, 1, 7, 1, 7],
+        '4' => [5, 5, 7, 1, 1],
+        '5' => [7, 4, 7, 1, 7],
+        '6' => [7, 4, 7, 5, 7],
+        '7' => [7, 1, 2, 2, 2],
+        '8' => [7, 5, 7, 5, 7],
+        '9' => [7, 5, 7, 1, 7],
+        'A' => [2, 5, 7, 5, 5],
+        'B' => [6, 5, 6, 5, 6],
+        'C' => [3, 4, 4, 4, 3],
+        'D' => [6, 5, 5, 5, 6],
+        'E' => [7, 4, 6, 4, 7],
+        'F' => [7, 4, 6, 4, 4],
+        'G' => [3, 4, 5, 5, 3],
+        'H' => [5, 5, 7, 5, 5],
+        'I' => [7, 2, 2, 2, 7],
+        'J' => [1, 1, 1, 5, 2],
+        'K' => [5, 5, 6, 5, 5],
+        'L' => [4, 4, 4, 4, 7],
+        'M' => [5, 7, 7, 5, 5],
+        'N' => [6, 5, 5, 5, 5],
+        'O' => [2, 5, 5, 5, 2],
+        'P' => [6, 5, 6, 4, 4],
+        'Q' => [2, 5, 5, 6, 3],
+        'R' => [6, 5, 6, 5, 5],
+        'S' => [3, 4, 2, 1, 6],
+        'T' => [7, 2, 2, 2, 2],
+        'U' => [5, 5, 5, 5, 7],
+        'V' => [5, 5, 5, 5, 2],
+        'W' => [5, 5, 7, 7, 5],
+        'X' => [5, 5, 2, 5, 5],
+        'Y' => [5, 5, 2, 2, 2],
+        'Z' => [7, 1, 2, 4, 7],
+        ':' => [0, 2, 0, 2, 0],
+        '.' => [0, 0, 0, 0, 2],
+        '%' => [5, 1, 2, 4, 5],
+        '-' => [0, 0, 7, 0, 0],
+        '_' => [0, 0, 0, 0, 7],
+        '/' => [1, 1, 2, 4, 4],
+        '#' => [5, 7, 5, 7, 5],
+        ' ' => [0, 0, 0, 0, 0],
+        _ => [7, 1, 2, 0, 2],
+    }
+}
+
+/// Amount of characters that fit into the given width at the given scale
+pub(crate) fn characters_fitting(width: u32, scale: u32) -> usize {
+    ((width + GLYPH_SPACING * scale) / ((GLYPH_WIDTH + GLYPH_SPACING) * scale)) as usize
+}
+
+/// Draws the text with its top left corner at `x`/`y`, everything outside of the image is cut off.
+pub(crate) fn draw_text(
+    imgbuf: &mut ImageBuffer<Rgba<u8>, Vec<u8>>,
+    x: u32,
+    y: u32,
+    text: &str,
+    scale: u32,
+    colour: Rgba<u8>,
+) {
+    for (index, character) in text.chars().enumerate() {
+        let left = x + index as u32 * (GLYPH_WIDTH + GLYPH_SPACING) * scale;
+
+        for (row, bits) in glyph(character).iter().enumerate() {
+            for column in 0..GLYPH_WIDTH {
+                if bits & (1 << (GLYPH_WIDTH - 1 - column)) == 0 {
+                    continue;
+                }
+
+                for dx in 0..scale {
+                    for dy in 0..scale {
+                        let px = left + column * scale + dx;
+                        let py = y + row as u32 * scale + dy;
+                        if px < imgbuf.width() && py < imgbuf.height() {
+                            imgbuf.put_pixel(px, py, colour);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,22 @@
+//! Parsing, replaying, analysing and rendering of minesweeper games. The providers fetch games
+//! through an [`provider::http::HttpClient`], so nothing depends on a specific runtime.
+
+pub mod analysis;
+pub mod base36;
+mod base62;
+pub mod compare;
+pub mod error;
+mod font;
+pub mod minesweeper_logic;
+pub mod output;
+mod palette;
+pub mod parsers;
+pub mod profile;
+pub mod provider;
+pub mod renderer;
+pub mod stats;
+#[cfg(test)]
+mod tests;
+pub mod text;
+mod textures;
+pub mod video;
@@ -0,0 +1,117 @@
+use crate::parsers::parser::Metadata;
+
+#[derive(Debug, Clone)]
+pub struct Board {
+    pub fields: Vec<Vec<Field>>,
+    pub changed_fields: Vec<Vec<bool>>,
+    pub metadata: Metadata,
+    pub open_fields: u32,
+    pub mine_count: u32,
+    pub total_fields: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Field {
+    pub value: u8,
+    pub field_state: FieldState,
+    pub mine: bool,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum FieldState {
+    Open,
+    Closed,
+    Flagged,
+    UnsureFlagged,
+}
+
+impl Board {
+    pub(crate) fn open_field(&mut self, x: usize, y: usize) {
+        // Cascades are worked off with a stack, as recursion overflows on large empty boards
+        let mut pending = vec![(x, y)];
+
+        while let Some((x, y)) = pending.pop() {
+            let field = &mut self.fields[y][x];
+
+            //If flagged or already open skip it
+            if field.field_state != FieldState::Closed {
+                continue;
+            }
+
+            field.field_state = FieldState::Open;
+            self.changed_fields[y][x] = true;
+            self.open_fields += 1;
+
+            if !field.mine && field.value == 0 {
+                pending.extend(self.neighbours(x, y));
+            }
+        }
+    }
+
+    /// Opens all closed neighbours of an open number once enough flags are placed around it.
+    /// Wrong flags make this open a mine, just like in the game.
+    pub(crate) fn chord_field(&mut self, x: usize, y: usize) {
+        for (xx, yy) in self.chord_targets(x, y) {
+            self.open_field(xx, yy);
+        }
+    }
+
+    /// Returns the cells a chord on the given cell would open, empty if the chord has no effect.
+    pub(crate) fn chord_targets(&self, x: usize, y: usize) -> Vec<(usize, usize)> {
+        let field = &self.fields[y][x];
+        if field.field_state != FieldState::Open || field.mine || field.value == 0 {
+            return Vec::new();
+        }
+
+        let neighbours = self.neighbours(x, y);
+        let flags = neighbours
+            .iter()
+            .filter(|(xx, yy)| self.fields[*yy][*xx].field_state == FieldState::Flagged)
+            .count();
+
+        if flags != field.value as usize {
+            return Vec::new();
+        }
+
+        neighbours
+            .into_iter()
+            .filter(|(xx, yy)| self.fields[*yy][*xx].field_state == FieldState::Closed)
+            .collect()
+    }
+
+    pub(crate) fn neighbours(&self, x: usize, y: usize) -> Vec<(usize, usize)> {
+        let mut neighbours = Vec::with_capacity(8);
+
+        for yd in -1..=1_i32 {
+            for xd in -1..=1_i32 {
+                let xx = xd + x as i32;
+                let yy = yd + y as i32;
+                if xx < 0
+                    || xx >= self.metadata.x_size
+                    || yy < 0
+                    || yy >= self.metadata.y_size
+                    || xd == 0 && yd == 0
+                {
+                    continue;
+                }
+                neighbours.push((xx as usize, yy as usize));
+            }
+        }
+
+        neighbours
+    }
+
+    pub(crate) fn calculate_done_percentage(&self) -> u32 {
+        ((self.open_fields as f32 / (self.total_fields - self.mine_count) as f32) * 100_f32) as u32
+    }
+}
+
+impl Field {
+    pub(crate) fn new() -> Self {
+        Field {
+            value: 0,
+            field_state: FieldState::Closed,
+            mine: false,
+        }
+    }
+}
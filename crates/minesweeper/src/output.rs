@@ -0,0 +1,91 @@
+use crate::analysis::overlay::{build_overlay, OverlayType};
+use crate::error::MinesweeperError;
+use crate::parsers::parser::ParsedData;
+use crate::renderer::{GifStream, ProgressStyle, RenderType, Renderer};
+use crate::video::AviStream;
+
+/// Everything that changes how a game gets rendered.
+#[derive(Copy, Clone, Default)]
+pub struct RenderOptions {
+    pub render_type: RenderType,
+    pub overlay: Option<OverlayType>,
+    pub progress_style: ProgressStyle,
+}
+
+/// Rendered game, animations are only encoded while they get read.
+pub enum RenderOutput {
+    Image(Vec<u8>),
+    Gif(Box<GifStream>),
+    Video(AviStream),
+    Svg(String),
+    Text(String),
+}
+
+impl RenderOutput {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            RenderOutput::Image(_) => "image/png",
+            RenderOutput::Gif(_) => "image/gif",
+            RenderOutput::Video(_) => "video/x-msvideo",
+            RenderOutput::Svg(_) => "image/svg+xml",
+            RenderOutput::Text(_) => "text/plain; charset=utf-8",
+        }
+    }
+
+    /// Usual file extension of the output
+    pub fn extension(&self) -> &'static str {
+        match self {
+            RenderOutput::Image(_) => "png",
+            RenderOutput::Gif(_) => "gif",
+            RenderOutput::Video(_) => "avi",
+            RenderOutput::Svg(_) => "svg",
+            RenderOutput::Text(_) => "txt",
+        }
+    }
+
+    /// Encodes the whole output at once, for callers that can not stream it.
+    pub fn into_bytes(self) -> Result<Vec<u8>, MinesweeperError> {
+        match self {
+            RenderOutput::Image(data) => Ok(data),
+            RenderOutput::Gif(gif_stream) => {
+                Ok(gif_stream.collect::<Result<Vec<_>, _>>()?.concat())
+            }
+            RenderOutput::Video(avi_stream) => {
+                Ok(avi_stream.collect::<Result<Vec<_>, _>>()?.concat())
+            }
+            RenderOutput::Svg(text) | RenderOutput::Text(text) => Ok(text.into_bytes()),
+        }
+    }
+}
+
+pub fn render(
+    parsed_data: ParsedData,
+    options: RenderOptions,
+) -> Result<RenderOutput, MinesweeperError> {
+    let overlay = options
+        .overlay
+        .map(|overlay| build_overlay(overlay, &parsed_data));
+
+    let gif = matches!(options.render_type, RenderType::Gif | RenderType::Video);
+    let mut renderer =
+        Renderer::from_parsed(parsed_data, gif).with_progress_style(options.progress_style);
+    if let Some(overlay) = overlay {
+        renderer = renderer.with_overlay(overlay);
+    }
+
+    Ok(match options.render_type {
+        RenderType::Image => RenderOutput::Image(
+            renderer
+                .render_jpeg()
+                .map_err(|_| MinesweeperError::ImageRender)?,
+        ),
+        RenderType::Gif => RenderOutput::Gif(Box::new(
+            renderer
+                .into_gif_stream()
+                .map_err(|_| MinesweeperError::ImageRender)?,
+        )),
+        RenderType::Video => RenderOutput::Video(AviStream::new(renderer.into_frames())?),
+        RenderType::Svg => RenderOutput::Svg(renderer.render_svg()),
+        RenderType::Text(style) => RenderOutput::Text(renderer.render_text(style)),
+    })
+}
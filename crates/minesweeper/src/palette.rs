@@ -0,0 +1,105 @@
+use std::collections::{HashMap, HashSet};
+
+use color_quant::NeuQuant;
+use image::{ImageBuffer, Rgba};
+
+/// Index that is reserved for transparent pixels, which keep the previous frame visible
+const TRANSPARENT_INDEX: u8 = 0;
+/// Colours left next to the transparent index
+const MAX_COLOURS: usize = 255;
+
+/// Fixed gif palette built from every colour a frame can contain, so frames only need to be indexed
+/// instead of being quantized one by one.
+pub(crate) struct Palette {
+    /// Global colour table with three bytes per entry
+    rgb: Vec<u8>,
+    lookup: HashMap<[u8; 3], u8>,
+}
+
+impl Palette {
+    /// Builds an exact palette if there are at most 255 colours,
+    /// otherwise the colours get reduced once with NeuQuant.
+    pub(crate) fn new(colours: impl IntoIterator<Item = Rgba<u8>>) -> Palette {
+        let mut colours: Vec<[u8; 3]> = colours
+            .into_iter()
+            .filter(|colour| colour.0[3] != 0)
+            .map(|colour| [colour.0[0], colour.0[1], colour.0[2]])
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        colours.sort_unstable();
+
+        let mut rgb = vec![0_u8; 3];
+
+        if colours.len() <= MAX_COLOURS {
+            rgb.extend(colours.iter().flatten());
+            let lookup = colours
+                .into_iter()
+                .zip(TRANSPARENT_INDEX + 1..=u8::MAX)
+                .collect();
+
+            return Palette { rgb, lookup };
+        }
+
+        let pixels: Vec<u8> = colours
+            .iter()
+            .flat_map(|[r, g, b]| [*r, *g, *b, u8::MAX])
+            .collect();
+        let quantizer = NeuQuant::new(10, MAX_COLOURS, &pixels);
+        rgb.extend(quantizer.color_map_rgb());
+
+        let mut palette = Palette {
+            rgb,
+            lookup: HashMap::new(),
+        };
+        palette.lookup = colours
+            .into_iter()
+            .map(|colour| (colour, palette.nearest(colour)))
+            .collect();
+
+        palette
+    }
+
+    /// Global colour table to pass to the encoder
+    pub(crate) fn rgb(&self) -> &[u8] {
+        &self.rgb
+    }
+
+    pub(crate) fn transparent(&self) -> u8 {
+        TRANSPARENT_INDEX
+    }
+
+    /// Maps every pixel of the frame to its palette index.
+    pub(crate) fn index_frame(&self, image: &ImageBuffer<Rgba<u8>, Vec<u8>>) -> Vec<u8> {
+        image
+            .pixels()
+            .map(|pixel| {
+                if pixel.0[3] == 0 {
+                    return TRANSPARENT_INDEX;
+                }
+
+                let colour = [pixel.0[0], pixel.0[1], pixel.0[2]];
+                self.lookup
+                    .get(&colour)
+                    .copied()
+                    .unwrap_or_else(|| self.nearest(colour))
+            })
+            .collect()
+    }
+
+    /// Closest palette entry by squared distance, only needed for colours missing from the lookup.
+    fn nearest(&self, colour: [u8; 3]) -> u8 {
+        self.rgb
+            .chunks_exact(3)
+            .enumerate()
+            .skip(TRANSPARENT_INDEX as usize + 1)
+            .min_by_key(|(_, entry)| {
+                entry
+                    .iter()
+                    .zip(colour)
+                    .map(|(a, b)| (*a as i32 - b as i32).pow(2))
+                    .sum::<i32>()
+            })
+            .map_or(TRANSPARENT_INDEX, |(index, _)| index as u8)
+    }
+}
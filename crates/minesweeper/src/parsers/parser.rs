@@ -0,0 +1,256 @@
+use crate::error::MinesweeperError;
+use crate::minesweeper_logic::{Board, FieldState};
+use crate::parsers;
+use serde::{Deserialize, Serialize};
+
+/// Largest supported width and height of a board
+pub const MAX_BOARD_SIZE: i32 = 200;
+/// Longest game in milliseconds, longer ones only occur in corrupted data and would overflow while replaying
+pub const MAX_GAME_DURATION: i64 = 7 * 24 * 60 * 60 * 1000;
+
+pub trait Iparser {
+    fn supported_versions(&self) -> Vec<&str>;
+    fn parse_mine_data(&self, data: &str, metadata: &Metadata) -> Result<Board, MinesweeperError>;
+    fn parse_mine_locations(&self, data: &str) -> Result<Vec<(i32, i32)>, MinesweeperError>;
+    fn parse_flag_data(&self, data: &str) -> Result<Vec<FlagAction>, MinesweeperError>;
+    fn parse_open_data(&self, data: &str) -> Result<Vec<OpenAction>, MinesweeperError>;
+    /// Chords are an optional fifth section, versions that don't know them reject any chords
+    fn parse_chord_data(&self, data: &str) -> Result<Vec<ChordAction>, MinesweeperError> {
+        if !data.is_empty() {
+            return Err(MinesweeperError::DataParse);
+        }
+        Ok(Vec::new())
+    }
+    fn parse_meta_data(&self, data: &str) -> Result<Metadata, MinesweeperError>;
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ApiData {
+    #[serde(rename = "gameData")]
+    pub game_data: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Metadata {
+    pub x_size: i32,
+    pub y_size: i32,
+    pub timeunits: i32,
+    /// Board generator as reported by the provider, not part of the game data itself
+    pub generator: Option<Generator>,
+}
+
+/// The way the mines got placed, which decides what the first click is guaranteed to be.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum Generator {
+    /// Mines are placed without looking at the first click
+    Random,
+    /// The first click is never a mine
+    FirstClickSafe,
+    /// The first click is always a zero and opens an area
+    FirstClickOpening,
+    /// The board can be solved without ever guessing
+    NoGuess,
+    Unknown(String),
+}
+
+impl Generator {
+    /// Whether the generator guarantees that the first click is not a mine
+    pub fn safe_first_click(&self) -> bool {
+        matches!(
+            self,
+            Generator::FirstClickSafe | Generator::FirstClickOpening | Generator::NoGuess
+        )
+    }
+}
+
+impl From<&str> for Generator {
+    fn from(value: &str) -> Self {
+        let normalized: String = value
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_lowercase();
+
+        match normalized.as_str() {
+            "random" | "classic" | "default" => Generator::Random,
+            "safe" | "firstclicksafe" | "safefirstclick" | "safestart" => Generator::FirstClickSafe,
+            "opening" | "firstclickopening" | "openingfirstclick" | "zerostart" | "zero" => {
+                Generator::FirstClickOpening
+            }
+            "noguess" | "noguessing" | "solvable" | "nog" => Generator::NoGuess,
+            _ => Generator::Unknown(value.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FlagAction {
+    pub x: i32,
+    pub y: i32,
+    pub time: i64,
+    pub action: Action,
+    pub total_time: i64,
+}
+
+#[derive(Debug, Clone)]
+pub enum Action {
+    Place,
+    Remove,
+    Toggle,
+}
+
+#[derive(Debug, Clone)]
+pub struct OpenAction {
+    pub x: i32,
+    pub y: i32,
+    pub time: i64,
+    pub total_time: i64,
+}
+
+/// A middle click on an open number, which opens all unflagged neighbours
+/// once the amount of flags around it matches the number.
+#[derive(Debug, Clone)]
+pub struct ChordAction {
+    pub x: i32,
+    pub y: i32,
+    pub total_time: i64,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ActionType {
+    Open,
+    Flag,
+    Chord,
+}
+
+pub struct ParsedData {
+    pub metadata: Metadata,
+    pub game_board: Board,
+    pub open_data: Vec<OpenAction>,
+    pub flag_data: Vec<FlagAction>,
+    pub chord_data: Vec<ChordAction>,
+}
+
+/// Splits a raw game data string (`<version>=<meta>+<mines>+<opens>+<flags>[+<chords>]`) and runs it
+/// through the parser that supports the given version.
+///
+/// The chord section is only understood by v2, where it uses the same encoding as the opens.
+/// Game data of other versions fails to parse if it contains chords.
+pub fn parse_game_data(game_data: &str) -> Result<ParsedData, MinesweeperError> {
+    let (version, data) = game_data
+        .split_once('=')
+        .ok_or(MinesweeperError::DataParse)?;
+
+    let possible_parsers: Vec<&dyn Iparser> = vec![
+        &parsers::v1::parser::ParserV1,
+        &parsers::v2::parser::ParserV2,
+    ];
+
+    let Some(parser) = possible_parsers
+        .iter()
+        .find(|p| p.supported_versions().contains(&version))
+    else {
+        return Err(MinesweeperError::UnsupportedVersion);
+    };
+
+    let split: Vec<&str> = data.split('+').collect();
+    if split.len() < 4 {
+        return Err(MinesweeperError::DataParse);
+    }
+
+    let metadata = parser.parse_meta_data(split[0].trim())?;
+    if !(1..=MAX_BOARD_SIZE).contains(&metadata.x_size)
+        || !(1..=MAX_BOARD_SIZE).contains(&metadata.y_size)
+    {
+        return Err(MinesweeperError::UnsupportedBoardSize);
+    }
+
+    let parsed_data = ParsedData {
+        game_board: parser.parse_mine_data(split[1].trim(), &metadata)?,
+        open_data: parser.parse_open_data(split[2].trim())?,
+        flag_data: parser.parse_flag_data(split[3].trim())?,
+        chord_data: match split.get(4) {
+            Some(data) => parser.parse_chord_data(data.trim())?,
+            None => Vec::new(),
+        },
+        metadata,
+    };
+
+    // Actions outside of the board would otherwise panic while replaying them
+    let on_board = |x: i32, y: i32| {
+        (0..parsed_data.metadata.x_size).contains(&x)
+            && (0..parsed_data.metadata.y_size).contains(&y)
+    };
+    if !parsed_data
+        .open_data
+        .iter()
+        .all(|open| on_board(open.x, open.y))
+        || !parsed_data
+            .flag_data
+            .iter()
+            .all(|flag| on_board(flag.x, flag.y))
+        || !parsed_data
+            .chord_data
+            .iter()
+            .all(|chord| on_board(chord.x, chord.y))
+    {
+        return Err(MinesweeperError::DataParse);
+    }
+
+    // Every section counts its time on its own, so the last action of each is the latest one
+    let last_tick = [
+        parsed_data.open_data.last().map(|open| open.total_time),
+        parsed_data.flag_data.last().map(|flag| flag.total_time),
+        parsed_data.chord_data.last().map(|chord| chord.total_time),
+    ]
+    .into_iter()
+    .flatten()
+    .max()
+    .unwrap_or(0);
+    let duration = last_tick.checked_mul(parsed_data.metadata.timeunits as i64);
+    if parsed_data.metadata.timeunits < 1 || duration.is_none_or(|ms| ms > MAX_GAME_DURATION) {
+        return Err(MinesweeperError::DataParse);
+    }
+
+    Ok(parsed_data)
+}
+
+/// Adds the time since the previous action to the time of the whole game so far.
+/// Negative times and totals beyond an i64 only occur in corrupted data.
+pub(crate) fn add_time(total_time: i64, time: i64) -> Result<i64, MinesweeperError> {
+    if time < 0 {
+        return Err(MinesweeperError::DataParse);
+    }
+    total_time
+        .checked_add(time)
+        .ok_or(MinesweeperError::DataParse)
+}
+
+impl ParsedData {
+    pub fn with_generator(mut self, generator: Option<Generator>) -> ParsedData {
+        self.metadata.generator = generator.clone();
+        self.game_board.metadata.generator = generator;
+        self
+    }
+}
+
+impl FlagAction {
+    pub(crate) fn perform_action(&self, board: &mut Board) {
+        match self.action {
+            Action::Place => {
+                board.fields[self.y as usize][self.x as usize].field_state = FieldState::Flagged;
+                board.changed_fields[self.y as usize][self.x as usize] = true;
+            }
+            Action::Remove => {
+                board.fields[self.y as usize][self.x as usize].field_state = FieldState::Closed;
+                board.changed_fields[self.y as usize][self.x as usize] = true;
+            }
+            Action::Toggle => {
+                board.fields[self.y as usize][self.x as usize].field_state =
+                    FieldState::UnsureFlagged;
+                board.changed_fields[self.y as usize][self.x as usize] = true;
+            }
+        }
+    }
+}
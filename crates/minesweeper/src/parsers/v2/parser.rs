@@ -0,0 +1,253 @@
+use crate::base62::decode;
+use crate::error::MinesweeperError;
+use crate::minesweeper_logic::{Board, Field};
+use std::str::FromStr;
+
+use crate::parsers::parser::{
+    add_time, Action, ChordAction, FlagAction, Iparser, Metadata, OpenAction,
+};
+
+pub struct ParserV2;
+
+impl Iparser for ParserV2 {
+    fn supported_versions(&self) -> Vec<&str> {
+        vec!["2"]
+    }
+
+    fn parse_mine_data(&self, data: &str, metadata: &Metadata) -> Result<Board, MinesweeperError> {
+        let mines = self.parse_mine_locations(data)?;
+
+        let mut board = Board {
+            fields: vec![vec![Field::new(); metadata.x_size as usize]; metadata.y_size as usize],
+            changed_fields: vec![vec![true; metadata.x_size as usize]; metadata.y_size as usize],
+            metadata: metadata.clone(),
+            mine_count: mines.len() as u32,
+            open_fields: 0,
+            total_fields: (metadata.y_size * metadata.x_size) as u32,
+        };
+
+        for (x, y) in mines {
+            let field = board
+                .fields
+                .get_mut(y as usize)
+                .and_then(|row| row.get_mut(x as usize))
+                .ok_or(MinesweeperError::DataParse)?;
+            // Mines outside the board or listed twice would throw off the mine count
+            if field.mine {
+                return Err(MinesweeperError::DataParse);
+            }
+            field.mine = true;
+        }
+
+        for x in 0..metadata.x_size {
+            for y in 0..metadata.y_size {
+                let field = &mut board.fields[y as usize][x as usize];
+
+                if !field.mine {
+                    continue;
+                }
+
+                for xd in -1..=1_i32 {
+                    for zd in -1..=1_i32 {
+                        let xx = x + xd;
+                        let yy = y + zd;
+                        if xx < 0
+                            || xx >= metadata.x_size
+                            || yy < 0
+                            || yy >= metadata.y_size
+                            || (zd == 0 && xd == 0)
+                        {
+                            continue;
+                        }
+
+                        let checked_field = &mut board.fields[yy as usize][xx as usize];
+                        if checked_field.mine {
+                            continue;
+                        }
+
+                        checked_field.value += 1;
+                    }
+                }
+            }
+        }
+
+        Ok(board)
+    }
+
+    fn parse_mine_locations(&self, data: &str) -> Result<Vec<(i32, i32)>, MinesweeperError> {
+        let mut return_data = Vec::new();
+
+        if data.chars().count() == 0 {
+            return Ok(return_data);
+        }
+
+        let raw_open_fields_data: Vec<&str> = data.split(';').collect();
+
+        for raw_open_field in raw_open_fields_data {
+            if let Some(part) = raw_open_field.split_once('|') {
+                return_data.push((coordinate(part.0)?, coordinate(part.1)?));
+            } else {
+                let chars: Vec<char> = raw_open_field.chars().collect();
+                for chunk in chars.chunks(2) {
+                    let [x, y] = chunk else {
+                        return Err(MinesweeperError::DataParse);
+                    };
+                    return_data.push((coordinate(&x.to_string())?, coordinate(&y.to_string())?));
+                }
+            }
+        }
+
+        Ok(return_data)
+    }
+
+    fn parse_flag_data(&self, data: &str) -> Result<Vec<FlagAction>, MinesweeperError> {
+        let mut return_data = Vec::new();
+        let mut total_time = 0;
+
+        if data.chars().count() == 0 {
+            return Ok(return_data);
+        }
+
+        let raw_open_fields_data: Vec<&str> = data.split(';').collect();
+
+        for raw_open_field in raw_open_fields_data {
+            if raw_open_field.contains('|') {
+                let mut chars = raw_open_field.chars();
+
+                let action_type = chars.next_back().ok_or(MinesweeperError::DataParse)?;
+                let part_one = chars
+                    .as_str()
+                    .split_once('|')
+                    .ok_or(MinesweeperError::DataParse)?;
+                let part_two = part_one
+                    .1
+                    .split_once(':')
+                    .ok_or(MinesweeperError::DataParse)?;
+
+                let time = decode(part_two.1).ok_or(MinesweeperError::DataParse)?;
+
+                total_time = add_time(total_time, time)?;
+
+                return_data.push(FlagAction {
+                    x: coordinate(part_one.0)?,
+                    y: coordinate(part_two.0)?,
+                    time,
+                    action: get_flag_type(action_type)?,
+                    total_time,
+                });
+            } else {
+                let mut chars = raw_open_field.chars();
+
+                let x = coordinate(&chars.next().ok_or(MinesweeperError::DataParse)?.to_string())?;
+                let y = coordinate(&chars.next().ok_or(MinesweeperError::DataParse)?.to_string())?;
+                let action = get_flag_type(chars.next_back().ok_or(MinesweeperError::DataParse)?)?;
+                let time = decode(chars.as_str()).ok_or(MinesweeperError::DataParse)?;
+
+                total_time = add_time(total_time, time)?;
+
+                return_data.push(FlagAction {
+                    x,
+                    y,
+                    action,
+                    time,
+                    total_time,
+                });
+            }
+        }
+
+        Ok(return_data)
+    }
+
+    fn parse_open_data(&self, data: &str) -> Result<Vec<OpenAction>, MinesweeperError> {
+        let mut return_data = Vec::new();
+        let mut total_time = 0;
+
+        if data.chars().count() == 0 {
+            return Ok(return_data);
+        }
+
+        let raw_open_fields_data: Vec<&str> = data.split(';').collect();
+
+        for raw_open_field in raw_open_fields_data {
+            if let Some(part_one) = raw_open_field.split_once('|') {
+                let part_two = part_one
+                    .1
+                    .split_once(':')
+                    .ok_or(MinesweeperError::DataParse)?;
+
+                let time = decode(part_two.1).ok_or(MinesweeperError::DataParse)?;
+
+                total_time = add_time(total_time, time)?;
+
+                return_data.push(OpenAction {
+                    x: coordinate(part_one.0)?,
+                    y: coordinate(part_two.0)?,
+                    time,
+                    total_time,
+                });
+            } else {
+                let mut chars = raw_open_field.chars();
+
+                let x = coordinate(&chars.next().ok_or(MinesweeperError::DataParse)?.to_string())?;
+                let y = coordinate(&chars.next().ok_or(MinesweeperError::DataParse)?.to_string())?;
+                let time = decode(chars.as_str()).ok_or(MinesweeperError::DataParse)?;
+
+                total_time = add_time(total_time, time)?;
+
+                return_data.push(OpenAction {
+                    x,
+                    y,
+                    time,
+                    total_time,
+                });
+            }
+        }
+
+        Ok(return_data)
+    }
+
+    /// Chords are middle clicks on open numbers, encoded exactly like opens: `<x><y><time>`
+    /// or `<x>|<y>:<time>` separated by `;`, every time counting from the previous chord.
+    fn parse_chord_data(&self, data: &str) -> Result<Vec<ChordAction>, MinesweeperError> {
+        Ok(self
+            .parse_open_data(data)?
+            .into_iter()
+            .map(|open| ChordAction {
+                x: open.x,
+                y: open.y,
+                total_time: open.total_time,
+            })
+            .collect())
+    }
+
+    fn parse_meta_data(&self, data: &str) -> Result<Metadata, MinesweeperError> {
+        let data_split_1 = data.split_once('x').ok_or(MinesweeperError::DataParse)?;
+        let data_split_2 = data_split_1
+            .1
+            .split_once(',')
+            .ok_or(MinesweeperError::DataParse)?;
+        let number = |text: &str| i32::from_str(text).map_err(|_| MinesweeperError::DataParse);
+        Ok(Metadata {
+            x_size: number(data_split_1.0)?,
+            y_size: number(data_split_2.0)?,
+            timeunits: number(data_split_2.1)?,
+            generator: None,
+        })
+    }
+}
+
+/// Decodes a base 62 coordinate, too large ones fail instead of wrapping around
+fn coordinate(data: &str) -> Result<i32, MinesweeperError> {
+    decode(data)
+        .and_then(|value| i32::try_from(value).ok())
+        .ok_or(MinesweeperError::DataParse)
+}
+
+fn get_flag_type(raw_flag_type: char) -> Result<Action, MinesweeperError> {
+    match raw_flag_type {
+        'P' => Ok(Action::Place),
+        'R' => Ok(Action::Remove),
+        'T' => Ok(Action::Toggle),
+        _ => Err(MinesweeperError::DataParse),
+    }
+}
@@ -0,0 +1,189 @@
+use std::collections::BTreeMap;
+
+use image::{ImageBuffer, Rgba};
+use serde::Serialize;
+
+use crate::error::MinesweeperError;
+use crate::font::{draw_text, GLYPH_HEIGHT};
+use crate::provider::game_id::GameIdFormat;
+use crate::provider::http::HttpClient;
+use crate::provider::provider::{GamePlayer, Provider};
+
+/// Scale of the font on the profile card
+const CARD_SCALE: u32 = 3;
+/// Space around the text of the profile card in pixels
+const CARD_PADDING: u32 = 12;
+/// Space between two lines of the profile card in pixels
+const CARD_LINE_SPACING: u32 = 6;
+/// Width of the profile card in pixels
+const CARD_WIDTH: u32 = 360;
+/// Amount of best times shown on the profile card
+const CARD_BEST_TIMES: usize = 3;
+
+const CARD_BACKGROUND: Rgba<u8> = Rgba([40, 40, 40, 255]);
+const CARD_TITLE: Rgba<u8> = Rgba([255, 215, 0, 255]);
+const CARD_TEXT: Rgba<u8> = Rgba([255, 255, 255, 255]);
+
+/// Game of a player as listed by a provider
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentGame {
+    pub id: String,
+    pub won: bool,
+    /// Time taken in milliseconds
+    pub time: u64,
+    /// Board size as `<width>x<height>`, if the provider tells
+    pub board: Option<String>,
+    /// Everyone who took part in the game, if the provider tells
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub players: Option<Vec<GamePlayer>>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BestTime {
+    pub board: String,
+    /// Time taken in milliseconds
+    pub time: u64,
+}
+
+/// Everything the provider knows about a player, unsupported parts are left out.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerProfile {
+    pub uuid: String,
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recent_games: Option<Vec<RecentGame>>,
+    /// Share of the recent games which were won, from 0 to 1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub win_rate: Option<f64>,
+    /// Fastest won game per board size
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub best_times: Option<Vec<BestTime>>,
+}
+
+impl PlayerProfile {
+    pub fn new(uuid: &str, name: Option<String>, recent_games: Option<Vec<RecentGame>>) -> Self {
+        let win_rate = recent_games
+            .as_ref()
+            .filter(|games| !games.is_empty())
+            .map(|games| games.iter().filter(|game| game.won).count() as f64 / games.len() as f64);
+        let best_times = recent_games.as_ref().map(|games| best_times(games));
+
+        PlayerProfile {
+            uuid: uuid.to_string(),
+            name,
+            recent_games,
+            win_rate,
+            best_times,
+        }
+    }
+
+    /// Renders the name and the statistics of the player as a png.
+    pub fn render_card(&self) -> Result<Vec<u8>, MinesweeperError> {
+        let mut lines = vec![(
+            self.name.as_deref().unwrap_or(&self.uuid).to_string(),
+            CARD_TITLE,
+        )];
+        if let Some(games) = &self.recent_games {
+            lines.push((format!("GAMES: {}", games.len()), CARD_TEXT));
+        }
+        if let Some(win_rate) = self.win_rate {
+            lines.push((format!("WINS: {:.1}%", win_rate * 100.0), CARD_TEXT));
+        }
+        for best_time in self.best_times.iter().flatten().take(CARD_BEST_TIMES) {
+            lines.push((
+                format!(
+                    "BEST {}: {:.2}S",
+                    best_time.board,
+                    best_time.time as f64 / 1000.0
+                ),
+                CARD_TEXT,
+            ));
+        }
+
+        let line_height = GLYPH_HEIGHT * CARD_SCALE + CARD_LINE_SPACING;
+        let height = 2 * CARD_PADDING + lines.len() as u32 * line_height - CARD_LINE_SPACING;
+        let mut imgbuf = ImageBuffer::from_pixel(CARD_WIDTH, height, CARD_BACKGROUND);
+        for (index, (text, colour)) in lines.iter().enumerate() {
+            draw_text(
+                &mut imgbuf,
+                CARD_PADDING,
+                CARD_PADDING + index as u32 * line_height,
+                text,
+                CARD_SCALE,
+                *colour,
+            );
+        }
+
+        let mut buffer = Vec::new();
+        let mut encoder = png::Encoder::new(&mut buffer, CARD_WIDTH, height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder
+            .write_header()
+            .map_err(|_| MinesweeperError::PngEncoding)?;
+        writer
+            .write_image_data(imgbuf.as_raw())
+            .map_err(|_| MinesweeperError::PngEncoding)?;
+        writer.finish().map_err(|_| MinesweeperError::PngEncoding)?;
+
+        Ok(buffer)
+    }
+}
+
+/// Fetches whatever the provider supports about the player.
+/// Fails with [MinesweeperError::Unsupported] if the provider knows nothing about players at all.
+pub async fn fetch_profile<P: Provider>(
+    provider: &P,
+    client: &impl HttpClient,
+    uuid: &str,
+    config: &P::Config,
+) -> Result<PlayerProfile, MinesweeperError> {
+    let name = supported(provider.fetch_name(client, uuid).await)?.map(|player| player.name);
+    let recent_games = supported(provider.fetch_recent_games(client, uuid, config).await)?;
+
+    if name.is_none() && recent_games.is_none() {
+        return Err(MinesweeperError::Unsupported);
+    }
+    // Providers without a name lookup still list the player among the players of their games
+    let name = name.or_else(|| name_in_games(recent_games.as_deref()?, uuid));
+
+    Ok(PlayerProfile::new(uuid, name, recent_games))
+}
+
+/// Turns an unsupported feature into `None` while keeping real failures.
+fn supported<T>(result: Result<T, MinesweeperError>) -> Result<Option<T>, MinesweeperError> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(MinesweeperError::Unsupported) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+fn name_in_games(games: &[RecentGame], uuid: &str) -> Option<String> {
+    games
+        .iter()
+        .flat_map(|game| game.players.iter().flatten())
+        .find(|player| GameIdFormat::Uuid.normalize(&player.uuid).as_deref() == Some(uuid))
+        .map(|player| player.name.clone())
+}
+
+fn best_times(games: &[RecentGame]) -> Vec<BestTime> {
+    let mut best: BTreeMap<&str, u64> = BTreeMap::new();
+    for game in games.iter().filter(|game| game.won) {
+        let Some(board) = game.board.as_deref() else {
+            continue;
+        };
+        let time = best.entry(board).or_insert(game.time);
+        *time = (*time).min(game.time);
+    }
+
+    best.into_iter()
+        .map(|(board, time)| BestTime {
+            board: board.to_string(),
+            time,
+        })
+        .collect()
+}
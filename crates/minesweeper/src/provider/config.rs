@@ -0,0 +1,50 @@
+use std::collections::HashMap;
+
+use crate::error::MinesweeperError;
+
+/// Where the value of a setting comes from in the Worker environment.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ConfigKind {
+    /// Set with `wrangler secret put`
+    Secret,
+    /// Set in the `[vars]` of `wrangler.toml`
+    Var,
+}
+
+/// A setting a provider reads from the environment.
+#[derive(Copy, Clone, Debug)]
+pub struct ConfigKey {
+    pub name: &'static str,
+    pub kind: ConfigKind,
+}
+
+/// Raw values of the declared settings, resolved by whoever runs the provider.
+#[derive(Default, Debug)]
+pub struct ConfigValues {
+    values: HashMap<&'static str, String>,
+}
+
+impl ConfigValues {
+    pub fn insert(&mut self, name: &'static str, value: String) {
+        self.values.insert(name, value);
+    }
+
+    /// The value of the setting, empty values count as missing
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.values
+            .get(name)
+            .map(String::as_str)
+            .filter(|value| !value.is_empty())
+    }
+
+    pub fn require(&self, name: &'static str) -> Result<&str, MinesweeperError> {
+        self.get(name).ok_or(MinesweeperError::MissingConfig(name))
+    }
+}
+
+/// Typed settings of a provider, built from the values of the keys it declares.
+pub trait ProviderConfig: Sized {
+    const KEYS: &'static [ConfigKey];
+
+    fn from_values(values: &ConfigValues) -> Result<Self, MinesweeperError>;
+}
@@ -0,0 +1,369 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use crate::error::MinesweeperError;
+use crate::profile::RecentGame;
+use crate::provider::config::{ConfigKey, ConfigKind, ConfigValues, ProviderConfig};
+use crate::provider::custom::json_path::JsonPath;
+use crate::provider::game_id::GameIdFormat;
+use crate::provider::http::{fetch, HttpClient, HttpRequest};
+use crate::provider::provider::{ApiData, GamePlayer, Provider};
+
+/// Variable with a json object of the settings of every custom provider by its id, like
+/// `{"example": {"url": "https://api.example.com/game/{gameid}", "gameData": "$.data.gameData"}}`
+pub const CUSTOM_PROVIDERS: &str = "CUSTOM_PROVIDERS";
+/// Secret with a json object of the tokens by provider id, sent as is in the auth header
+pub const CUSTOM_PROVIDER_TOKENS: &str = "CUSTOM_PROVIDER_TOKENS";
+
+/// Fields of [ApiData] which can be read from the answer
+const FIELDS: [&str; 12] = [
+    "type",
+    "time",
+    "generator",
+    "uuid",
+    "correctFlags",
+    "incorrectFlags",
+    "won",
+    "mines",
+    "width",
+    "height",
+    "timeStart",
+    "timeEnd",
+];
+
+/// Provider for a server with its own api, set up through the environment of the Worker.
+pub struct CustomProvider {
+    id: String,
+    name: String,
+}
+
+/// Settings of every custom provider by its id
+pub struct CustomConfig {
+    pub servers: BTreeMap<String, CustomServer>,
+}
+
+/// Settings of a single custom provider
+pub struct CustomServer {
+    pub name: String,
+    /// Url of a game, `{gameid}` gets replaced with the id of the game
+    pub url: String,
+    /// Name and value of the auth header, no header is sent without a token
+    pub auth: Option<(String, String)>,
+    pub game_id_formats: Vec<GameIdFormat>,
+    /// Path to the game data string in the answer
+    pub game_data: JsonPath,
+    /// Paths to further fields of [ApiData]
+    pub fields: Vec<(&'static str, JsonPath)>,
+    pub players: Option<PlayerPaths>,
+    pub recent_games: Option<RecentGamePaths>,
+}
+
+/// Where the players of a game are, the paths of a player start at its entry in the list
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PlayerPaths {
+    pub list: JsonPath,
+    pub uuid: JsonPath,
+    pub name: JsonPath,
+    pub group: Option<JsonPath>,
+}
+
+/// Where the latest games of a player are, the paths of a game start at its entry in the list
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RecentGamePaths {
+    /// Url of the games of a player, `{uuid}` gets replaced with the uuid of the player
+    pub url: String,
+    pub list: JsonPath,
+    pub id: JsonPath,
+    pub won: JsonPath,
+    pub time: JsonPath,
+    pub board: Option<JsonPath>,
+    pub players: Option<PlayerPaths>,
+}
+
+/// Settings of a custom provider as written in [CUSTOM_PROVIDERS]
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct ServerSettings {
+    name: Option<String>,
+    url: String,
+    /// Defaults to `Authorization`
+    auth_header: Option<String>,
+    /// Defaults to `token`
+    #[serde(default)]
+    game_ids: Vec<String>,
+    game_data: JsonPath,
+    #[serde(default)]
+    fields: HashMap<String, JsonPath>,
+    players: Option<PlayerPaths>,
+    recent_games: Option<RecentGamePaths>,
+}
+
+impl ProviderConfig for CustomConfig {
+    const KEYS: &'static [ConfigKey] = &[
+        ConfigKey {
+            name: CUSTOM_PROVIDERS,
+            kind: ConfigKind::Var,
+        },
+        ConfigKey {
+            name: CUSTOM_PROVIDER_TOKENS,
+            kind: ConfigKind::Secret,
+        },
+    ];
+
+    fn from_values(values: &ConfigValues) -> Result<Self, MinesweeperError> {
+        let settings: BTreeMap<String, ServerSettings> =
+            serde_json::from_str(values.require(CUSTOM_PROVIDERS)?)
+                .map_err(|_| MinesweeperError::InvalidConfig(CUSTOM_PROVIDERS))?;
+        let mut tokens: HashMap<String, String> = match values.get(CUSTOM_PROVIDER_TOKENS) {
+            Some(tokens) => serde_json::from_str(tokens)
+                .map_err(|_| MinesweeperError::InvalidConfig(CUSTOM_PROVIDER_TOKENS))?,
+            None => HashMap::new(),
+        };
+
+        let servers = settings
+            .into_iter()
+            .map(|(id, settings)| {
+                let token = tokens.remove(&id);
+                let server = CustomServer::new(&id, settings, token)?;
+                Ok((id, server))
+            })
+            .collect::<Result<_, MinesweeperError>>()?;
+        // A token without a provider is most likely a typo in the id
+        if !tokens.is_empty() {
+            return Err(MinesweeperError::InvalidConfig(CUSTOM_PROVIDER_TOKENS));
+        }
+
+        Ok(CustomConfig { servers })
+    }
+}
+
+impl CustomConfig {
+    /// Every configured provider, to be offered next to the built in ones
+    pub fn providers(&self) -> impl Iterator<Item = CustomProvider> + '_ {
+        self.servers.iter().map(|(id, server)| CustomProvider {
+            id: id.clone(),
+            name: server.name.clone(),
+        })
+    }
+
+    fn server(&self, id: &str) -> Result<&CustomServer, MinesweeperError> {
+        self.servers
+            .get(id)
+            .ok_or(MinesweeperError::MissingConfig(CUSTOM_PROVIDERS))
+    }
+}
+
+impl CustomServer {
+    fn new(
+        id: &str,
+        settings: ServerSettings,
+        token: Option<String>,
+    ) -> Result<Self, MinesweeperError> {
+        let recent_games_url = settings.recent_games.as_ref().map(|paths| &paths.url);
+        if !settings.url.contains("{gameid}")
+            || recent_games_url.is_some_and(|url| !url.contains("{uuid}"))
+        {
+            return Err(MinesweeperError::InvalidConfig(CUSTOM_PROVIDERS));
+        }
+
+        let game_id_formats = match settings.game_ids.is_empty() {
+            true => vec![GameIdFormat::Token],
+            false => settings
+                .game_ids
+                .iter()
+                .map(|format| format.parse())
+                .collect::<Result<_, _>>()
+                .map_err(|_| MinesweeperError::InvalidConfig(CUSTOM_PROVIDERS))?,
+        };
+
+        let mut fields = settings
+            .fields
+            .into_iter()
+            .map(|(name, path)| {
+                let name = FIELDS
+                    .into_iter()
+                    .find(|known| *known == name.trim())
+                    .ok_or(MinesweeperError::InvalidConfig(CUSTOM_PROVIDERS))?;
+                Ok((name, path))
+            })
+            .collect::<Result<Vec<_>, MinesweeperError>>()?;
+        fields.sort_by_key(|(name, _)| *name);
+
+        let auth = token.map(|token| {
+            let header = settings.auth_header.as_deref().unwrap_or("Authorization");
+            (header.to_string(), token)
+        });
+
+        Ok(CustomServer {
+            name: settings.name.unwrap_or_else(|| id.to_string()),
+            url: settings.url,
+            auth,
+            game_id_formats,
+            game_data: settings.game_data,
+            fields,
+            players: settings.players,
+            recent_games: settings.recent_games,
+        })
+    }
+
+    fn request(&self, url: String) -> HttpRequest {
+        let request = HttpRequest::get(url);
+        match &self.auth {
+            Some((header, token)) => request.with_header(header, token),
+            None => request,
+        }
+    }
+}
+
+impl PlayerPaths {
+    /// The players listed in the value, `None` if it has no list
+    fn read(&self, value: &Value) -> Result<Option<Vec<GamePlayer>>, MinesweeperError> {
+        let Some(list) = self.list.select(value).filter(|list| !list.is_null()) else {
+            return Ok(None);
+        };
+
+        list.as_array()
+            .ok_or(MinesweeperError::ApiDataParse)?
+            .iter()
+            .map(|player| {
+                let text = |path: &JsonPath| path.select(player).and_then(Value::as_str);
+                Ok(GamePlayer {
+                    uuid: text(&self.uuid)
+                        .ok_or(MinesweeperError::ApiDataParse)?
+                        .to_string(),
+                    name: text(&self.name)
+                        .ok_or(MinesweeperError::ApiDataParse)?
+                        .to_string(),
+                    group: self.group.as_ref().and_then(text).map(str::to_string),
+                })
+            })
+            .collect::<Result<_, _>>()
+            .map(Some)
+    }
+}
+
+impl Provider for CustomProvider {
+    type Config = CustomConfig;
+
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn game_id_formats(&self, config: &CustomConfig) -> Vec<GameIdFormat> {
+        config
+            .server(&self.id)
+            .map(|server| server.game_id_formats.clone())
+            .unwrap_or_default()
+    }
+
+    async fn fetch_data(
+        &self,
+        client: &impl HttpClient,
+        game_id: &str,
+        config: &CustomConfig,
+    ) -> Result<ApiData, MinesweeperError> {
+        let server = config.server(&self.id)?;
+        let game_id = self.normalize_game_id(game_id, config)?;
+        let request = server.request(server.url.replace("{gameid}", &encode(&game_id)));
+        let answer = fetch(client, request).await?.json::<Value>()?;
+
+        let game_data = server
+            .game_data
+            .select(&answer)
+            .and_then(Value::as_str)
+            .ok_or(MinesweeperError::GameDataNotFound)?;
+
+        let mut data = Map::new();
+        data.insert("gameData".to_string(), game_data.into());
+        for (field, path) in &server.fields {
+            if let Some(value) = path.select(&answer).filter(|value| !value.is_null()) {
+                data.insert(field.to_string(), value.clone());
+            }
+        }
+
+        data.entry("uuid").or_insert(Value::from(""));
+
+        let mut api_data: ApiData = serde_json::from_value(Value::Object(data))
+            .map_err(|_| MinesweeperError::ApiDataParse)?;
+        if let Some(players) = &server.players {
+            api_data.players = players.read(&answer)?;
+        }
+
+        Ok(api_data)
+    }
+
+    async fn fetch_recent_games(
+        &self,
+        client: &impl HttpClient,
+        uuid: &str,
+        config: &CustomConfig,
+    ) -> Result<Vec<RecentGame>, MinesweeperError> {
+        let server = config.server(&self.id)?;
+        let Some(paths) = &server.recent_games else {
+            return Err(MinesweeperError::Unsupported);
+        };
+        let request = server.request(paths.url.replace("{uuid}", &encode(uuid)));
+        let answer = fetch(client, request).await?.json::<Value>()?;
+
+        paths
+            .list
+            .select(&answer)
+            .and_then(Value::as_array)
+            .ok_or(MinesweeperError::ApiDataParse)?
+            .iter()
+            .map(|game| {
+                let id = match paths.id.select(game) {
+                    Some(Value::String(id)) => id.clone(),
+                    Some(Value::Number(id)) => id.to_string(),
+                    _ => return Err(MinesweeperError::ApiDataParse),
+                };
+                let players = match &paths.players {
+                    Some(players) => players.read(game)?,
+                    None => None,
+                };
+
+                Ok(RecentGame {
+                    id,
+                    won: paths
+                        .won
+                        .select(game)
+                        .and_then(Value::as_bool)
+                        .ok_or(MinesweeperError::ApiDataParse)?,
+                    time: paths
+                        .time
+                        .select(game)
+                        .and_then(Value::as_u64)
+                        .ok_or(MinesweeperError::ApiDataParse)?,
+                    board: paths
+                        .board
+                        .as_ref()
+                        .and_then(|path| path.select(game))
+                        .and_then(Value::as_str)
+                        .map(str::to_string),
+                    players,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Percent encodes everything except unreserved characters, so the id can't change the url.
+fn encode(game_id: &str) -> String {
+    game_id
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
@@ -0,0 +1,89 @@
+use std::str::FromStr;
+
+use serde::{de, Deserialize, Deserializer};
+use serde_json::Value;
+
+/// Step from a json value to one of its children
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+/// The small part of JSONPath needed to point at a single value, like `$.data.games[0].notation`.
+/// Keys with other characters than letters, digits, `_` and `-` are written as `$['game data']`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JsonPath {
+    segments: Vec<Segment>,
+}
+
+impl JsonPath {
+    /// The value the path points at, if the json has it
+    pub fn select<'a>(&self, value: &'a Value) -> Option<&'a Value> {
+        self.segments
+            .iter()
+            .try_fold(value, |value, segment| match segment {
+                Segment::Key(key) => value.get(key),
+                Segment::Index(index) => value.get(index),
+            })
+    }
+}
+
+impl FromStr for JsonPath {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut rest = s
+            .trim()
+            .strip_prefix('$')
+            .ok_or(format!("The path {s} does not start with $"))?;
+        let mut segments = Vec::new();
+
+        while !rest.is_empty() {
+            if let Some(after_dot) = rest.strip_prefix('.') {
+                let end = after_dot
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '-'))
+                    .unwrap_or(after_dot.len());
+                if end == 0 {
+                    return Err(format!("The path {s} has an empty key"));
+                }
+                segments.push(Segment::Key(after_dot[..end].to_string()));
+                rest = &after_dot[end..];
+            } else if let Some(after_bracket) = rest.strip_prefix('[') {
+                let (inner, after) = after_bracket
+                    .split_once(']')
+                    .ok_or(format!("The path {s} has an unclosed ["))?;
+                let quoted = inner
+                    .strip_prefix('\'')
+                    .and_then(|inner| inner.strip_suffix('\''))
+                    .or_else(|| {
+                        inner
+                            .strip_prefix('"')
+                            .and_then(|inner| inner.strip_suffix('"'))
+                    });
+                segments.push(match quoted {
+                    Some(key) => Segment::Key(key.to_string()),
+                    None => Segment::Index(
+                        inner
+                            .parse()
+                            .map_err(|_| format!("The path {s} has an invalid index {inner}"))?,
+                    ),
+                });
+                rest = after;
+            } else {
+                return Err(format!("The path {s} has an unexpected {rest}"));
+            }
+        }
+
+        Ok(JsonPath { segments })
+    }
+}
+
+/// Paths in settings are written as strings
+impl<'de> Deserialize<'de> for JsonPath {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
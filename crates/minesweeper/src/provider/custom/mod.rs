@@ -0,0 +1,2 @@
+pub mod custom_provider;
+pub mod json_path;
@@ -0,0 +1,102 @@
+use std::str::FromStr;
+
+use crate::base36;
+use crate::error::MinesweeperError;
+
+/// Longest id accepted as a [GameIdFormat::Token]
+const MAX_TOKEN_LENGTH: usize = 64;
+/// Marks an id as a [GameIdFormat::PrefixedNumeric]
+const NUMERIC_PREFIX: &str = "id:";
+
+/// Way a provider identifies its games.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum GameIdFormat {
+    /// Short id in base 36 like `zz`, normalized to the number it stands for
+    Base36,
+    /// Plain number like `1295`
+    Numeric,
+    /// Plain number behind `id:` like `id:1295`, for providers whose short ids can be all digits
+    PrefixedNumeric,
+    /// UUID with or without hyphens, normalized to the lowercase hyphenated form
+    Uuid,
+    /// Letters, digits, `-` and `_`, passed on unchanged
+    Token,
+}
+
+impl GameIdFormat {
+    /// The id in the form the provider expects, if it has this format
+    pub fn normalize(&self, game_id: &str) -> Option<String> {
+        match self {
+            GameIdFormat::Base36 => base36::decode(game_id).map(|id| id.to_string()),
+            GameIdFormat::Numeric => game_id
+                .parse::<u64>()
+                .ok()
+                .filter(|_| game_id.bytes().all(|byte| byte.is_ascii_digit()))
+                .map(|id| id.to_string()),
+            GameIdFormat::PrefixedNumeric => game_id
+                .strip_prefix(NUMERIC_PREFIX)
+                .and_then(|id| GameIdFormat::Numeric.normalize(id)),
+            GameIdFormat::Uuid => normalize_uuid(game_id),
+            GameIdFormat::Token => Some(game_id.to_string()).filter(|_| {
+                (1..=MAX_TOKEN_LENGTH).contains(&game_id.len())
+                    && game_id
+                        .bytes()
+                        .all(|byte| byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_')
+            }),
+        }
+    }
+}
+
+impl FromStr for GameIdFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "base36" => Ok(GameIdFormat::Base36),
+            "numeric" => Ok(GameIdFormat::Numeric),
+            "prefixed-numeric" => Ok(GameIdFormat::PrefixedNumeric),
+            "uuid" => Ok(GameIdFormat::Uuid),
+            "token" => Ok(GameIdFormat::Token),
+            _ => Err(format!("Unknown game id format {s}")),
+        }
+    }
+}
+
+/// Normalizes the id with the first format it has.
+pub fn normalize_game_id(
+    formats: &[GameIdFormat],
+    game_id: &str,
+) -> Result<String, MinesweeperError> {
+    let game_id = game_id.trim();
+
+    formats
+        .iter()
+        .find_map(|format| format.normalize(game_id))
+        .ok_or_else(|| MinesweeperError::InvalidGameId(game_id.to_string()))
+}
+
+fn normalize_uuid(uuid: &str) -> Option<String> {
+    let hex: String = match uuid.len() {
+        32 => uuid.to_string(),
+        36 if [8, 13, 18, 23]
+            .iter()
+            .all(|&index| uuid.as_bytes()[index] == b'-') =>
+        {
+            uuid.replace('-', "")
+        }
+        _ => return None,
+    };
+    if hex.len() != 32 || !hex.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let hex = hex.to_ascii_lowercase();
+    Some(format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    ))
+}
@@ -0,0 +1,64 @@
+use crate::error::MinesweeperError;
+use crate::provider::config::{ConfigKey, ConfigValues, ProviderConfig};
+use crate::provider::game_id::GameIdFormat;
+use crate::provider::http::{fetch, HttpClient, HttpRequest};
+use crate::provider::provider::{ApiData, PlayerData, Provider};
+
+pub struct GreevProvider;
+
+/// The api of Greev is public and needs no settings
+pub struct GreevConfig;
+
+impl ProviderConfig for GreevConfig {
+    const KEYS: &'static [ConfigKey] = &[];
+
+    fn from_values(_: &ConfigValues) -> Result<Self, MinesweeperError> {
+        Ok(GreevConfig)
+    }
+}
+
+impl Provider for GreevProvider {
+    type Config = GreevConfig;
+
+    fn id(&self) -> &str {
+        "greev"
+    }
+
+    fn name(&self) -> &str {
+        "Greev"
+    }
+
+    fn game_id_formats(&self, _: &GreevConfig) -> Vec<GameIdFormat> {
+        vec![GameIdFormat::Token]
+    }
+
+    async fn fetch_data(
+        &self,
+        client: &impl HttpClient,
+        gameid: &str,
+        config: &GreevConfig,
+    ) -> Result<ApiData, MinesweeperError> {
+        let gameid = self.normalize_game_id(gameid, config)?;
+        fetch(
+            client,
+            HttpRequest::get(format!(
+                "https://api.greev.eu/v2/stats/minesweeper/game/{gameid}"
+            )),
+        )
+        .await?
+        .json::<ApiData>()
+    }
+
+    async fn fetch_name(
+        &self,
+        client: &impl HttpClient,
+        uuid: &str,
+    ) -> Result<PlayerData, MinesweeperError> {
+        fetch(
+            client,
+            HttpRequest::get(format!("https://api.greev.eu/v2/player/name/{uuid}")),
+        )
+        .await?
+        .json::<PlayerData>()
+    }
+}
@@ -0,0 +1,213 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+
+use crate::error::MinesweeperError;
+
+/// Time a provider gets to answer a single request
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+/// Attempts of a request before transient errors are given up on
+const MAX_ATTEMPTS: u32 = 3;
+/// Wait before the first retry, doubled for every further one
+const INITIAL_BACKOFF: Duration = Duration::from_millis(250);
+/// Longest `Retry-After` that is waited for, longer rate limits are returned right away
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(2);
+
+/// Sends the requests of the providers, so they work the same inside a Worker, natively and in tests.
+#[allow(async_fn_in_trait)]
+pub trait HttpClient {
+    /// Sends the request, failing with `UpstreamTimeout` once its timeout passed.
+    /// Error statuses are returned as responses.
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse, MinesweeperError>;
+    async fn sleep(&self, duration: Duration);
+}
+
+/// Sends the request and turns error statuses into errors.
+/// Server errors, timeouts and short rate limits are retried with backoff.
+pub async fn fetch(
+    client: &impl HttpClient,
+    request: HttpRequest,
+) -> Result<HttpResponse, MinesweeperError> {
+    let mut backoff = INITIAL_BACKOFF;
+    let mut attempt = 1;
+
+    loop {
+        let result = client.send(request.clone()).await.and_then(check_status);
+
+        let wait = match &result {
+            Err(MinesweeperError::RateLimited {
+                retry_after: Some(seconds),
+            }) => Some(Duration::from_secs(*seconds)).filter(|wait| *wait <= MAX_RETRY_AFTER),
+            Err(
+                MinesweeperError::RateLimited { retry_after: None }
+                | MinesweeperError::UpstreamServer(_)
+                | MinesweeperError::UpstreamTimeout
+                | MinesweeperError::UpstreamRequest,
+            ) => Some(backoff),
+            _ => None,
+        };
+
+        match wait {
+            Some(wait) if attempt < MAX_ATTEMPTS => {
+                client.sleep(wait).await;
+                backoff *= 2;
+                attempt += 1;
+            }
+            _ => return result,
+        }
+    }
+}
+
+fn check_status(response: HttpResponse) -> Result<HttpResponse, MinesweeperError> {
+    match response.status {
+        200..=299 => Ok(response),
+        401 | 403 => Err(MinesweeperError::UpstreamAuth),
+        404 | 410 => Err(MinesweeperError::UpstreamNotFound),
+        408 => Err(MinesweeperError::UpstreamTimeout),
+        429 => Err(MinesweeperError::RateLimited {
+            // Only the delay in seconds is supported, not the http date
+            retry_after: response
+                .header("Retry-After")
+                .and_then(|value| value.trim().parse().ok()),
+        }),
+        500..=599 => Err(MinesweeperError::UpstreamServer(response.status)),
+        status => Err(MinesweeperError::UpstreamStatus(status)),
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct HttpRequest {
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub timeout: Duration,
+}
+
+impl HttpRequest {
+    pub fn get(url: impl Into<String>) -> HttpRequest {
+        HttpRequest {
+            url: url.into(),
+            headers: Vec::new(),
+            timeout: REQUEST_TIMEOUT,
+        }
+    }
+
+    pub fn with_header(mut self, name: &str, value: &str) -> HttpRequest {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        find_header(&self.headers, name)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    pub fn new(status: u16, body: impl Into<Vec<u8>>) -> HttpResponse {
+        HttpResponse {
+            status,
+            headers: Vec::new(),
+            body: body.into(),
+        }
+    }
+
+    pub fn with_header(mut self, name: &str, value: &str) -> HttpResponse {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        find_header(&self.headers, name)
+    }
+
+    pub fn json<T: DeserializeOwned>(&self) -> Result<T, MinesweeperError> {
+        serde_json::from_slice(&self.body).map_err(|_| MinesweeperError::ApiDataParse)
+    }
+}
+
+/// Header names are case insensitive
+fn find_header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(header, _)| header.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
+
+/// Answers requests with canned responses instead of going over the network.
+/// Several responses for the same url are returned in order, the last one is repeated.
+/// Requests to unknown urls fail like an unreachable host. Sleeping returns right away.
+#[derive(Default)]
+pub struct MockClient {
+    responses: RefCell<HashMap<String, VecDeque<MockAnswer>>>,
+    requests: RefCell<Vec<HttpRequest>>,
+    sleeps: RefCell<Vec<Duration>>,
+}
+
+#[derive(Clone)]
+enum MockAnswer {
+    Response(HttpResponse),
+    Timeout,
+}
+
+impl MockClient {
+    pub fn with_response(self, url: &str, response: HttpResponse) -> MockClient {
+        self.with_answer(url, MockAnswer::Response(response))
+    }
+
+    /// Lets the request to the url run into its timeout
+    pub fn with_timeout(self, url: &str) -> MockClient {
+        self.with_answer(url, MockAnswer::Timeout)
+    }
+
+    fn with_answer(self, url: &str, answer: MockAnswer) -> MockClient {
+        self.responses
+            .borrow_mut()
+            .entry(url.to_string())
+            .or_default()
+            .push_back(answer);
+        self
+    }
+
+    /// Every request that was sent so far
+    pub fn requests(&self) -> Vec<HttpRequest> {
+        self.requests.borrow().clone()
+    }
+
+    /// Every wait between retries so far
+    pub fn sleeps(&self) -> Vec<Duration> {
+        self.sleeps.borrow().clone()
+    }
+}
+
+impl HttpClient for MockClient {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse, MinesweeperError> {
+        self.requests.borrow_mut().push(request.clone());
+
+        let mut responses = self.responses.borrow_mut();
+        let queue = responses
+            .get_mut(&request.url)
+            .ok_or(MinesweeperError::UpstreamRequest)?;
+        let answer = match queue.len() {
+            0 => return Err(MinesweeperError::UpstreamRequest),
+            1 => queue[0].clone(),
+            _ => queue.pop_front().unwrap(),
+        };
+
+        match answer {
+            MockAnswer::Response(response) => Ok(response),
+            MockAnswer::Timeout => Err(MinesweeperError::UpstreamTimeout),
+        }
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        self.sleeps.borrow_mut().push(duration);
+    }
+}
@@ -0,0 +1,163 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::MinesweeperError;
+use crate::profile::RecentGame;
+use crate::provider::config::{ConfigKey, ConfigKind, ConfigValues, ProviderConfig};
+use crate::provider::game_id::GameIdFormat;
+use crate::provider::http::{fetch, HttpClient, HttpRequest};
+use crate::provider::provider::{ApiData, GamePlayer, Provider};
+
+/// Secret holding the api key for McPlayHD
+pub const MCPLAYHD_API_KEY: &str = "MCPLAYHD_API_KEY";
+
+pub struct McPlayHdProvider;
+
+pub struct McPlayHdConfig {
+    pub api_key: String,
+}
+
+impl ProviderConfig for McPlayHdConfig {
+    const KEYS: &'static [ConfigKey] = &[ConfigKey {
+        name: MCPLAYHD_API_KEY,
+        kind: ConfigKind::Secret,
+    }];
+
+    fn from_values(values: &ConfigValues) -> Result<Self, MinesweeperError> {
+        Ok(McPlayHdConfig {
+            api_key: values.require(MCPLAYHD_API_KEY)?.to_string(),
+        })
+    }
+}
+
+impl Provider for McPlayHdProvider {
+    type Config = McPlayHdConfig;
+
+    fn id(&self) -> &str {
+        "mcplayhd"
+    }
+
+    fn name(&self) -> &str {
+        "McPlayHD"
+    }
+
+    /// Every plain number is a valid short id as well, so numeric ids need the `id:` prefix
+    fn game_id_formats(&self, _: &McPlayHdConfig) -> Vec<GameIdFormat> {
+        vec![GameIdFormat::PrefixedNumeric, GameIdFormat::Base36]
+    }
+
+    async fn fetch_data(
+        &self,
+        client: &impl HttpClient,
+        game_id: &str,
+        config: &McPlayHdConfig,
+    ) -> Result<ApiData, MinesweeperError> {
+        let id = self.normalize_game_id(game_id, config)?;
+
+        let request =
+            HttpRequest::get(format!("https://mcplayhd.net/api/v1/minesweeper/game/{id}"))
+                .with_header("Authorization", &format!("Bearer {}", config.api_key));
+        let Data { game_info, players } =
+            fetch(client, request).await?.json::<Response<Data>>()?.data;
+
+        Ok(ApiData {
+            game_data: Some(game_info.algebraic_notation),
+            tiepe: None,
+            time: Some(game_info.time_taken),
+            generator: None,
+            uuid: game_info.uuid,
+            correct_flags: Some(game_info.flags_correct),
+            incorrect_flags: Some(game_info.flags_incorrect),
+            won: Some(game_info.won),
+            mines: Some(game_info.mines),
+            width: Some(game_info.size_x),
+            height: Some(game_info.size_z),
+            time_start: Some(game_info.time_start),
+            time_end: Some(game_info.time_end),
+            players: Some(players.into_iter().map(GamePlayer::from).collect()),
+        })
+    }
+
+    /// The games of a player come with the same players list as a single game,
+    /// which is also where the name of the player is taken from
+    async fn fetch_recent_games(
+        &self,
+        client: &impl HttpClient,
+        uuid: &str,
+        config: &McPlayHdConfig,
+    ) -> Result<Vec<RecentGame>, MinesweeperError> {
+        let request = HttpRequest::get(format!(
+            "https://mcplayhd.net/api/v1/minesweeper/player/{uuid}/games"
+        ))
+        .with_header("Authorization", &format!("Bearer {}", config.api_key));
+        let games = fetch(client, request)
+            .await?
+            .json::<Response<Vec<Data>>>()?
+            .data;
+
+        Ok(games
+            .into_iter()
+            .map(|Data { game_info, players }| RecentGame {
+                // Plain numbers would be read as short ids
+                id: format!("id:{}", game_info.id),
+                won: game_info.won,
+                time: game_info.time_taken,
+                board: Some(format!("{}x{}", game_info.size_x, game_info.size_z)),
+                players: Some(players.into_iter().map(GamePlayer::from).collect()),
+            })
+            .collect())
+    }
+}
+
+impl From<Player> for GamePlayer {
+    fn from(player: Player) -> Self {
+        GamePlayer {
+            uuid: player.uuid,
+            name: player.name,
+            group: Some(player.group),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct GameInfo {
+    id: u32,
+    uuid: String,
+    won: bool,
+    #[serde(rename = "flagsCorrect")]
+    flags_correct: u32,
+    #[serde(rename = "flagsIncorrect")]
+    flags_incorrect: u32,
+    #[serde(rename = "timeStart")]
+    time_start: u64,
+    #[serde(rename = "timeEnd")]
+    time_end: u64,
+    #[serde(rename = "timeTaken")]
+    time_taken: u64,
+    mines: u32,
+    #[serde(rename = "sizeX")]
+    size_x: u32,
+    #[serde(rename = "sizeZ")]
+    size_z: u32,
+    #[serde(rename = "algebraicNotation")]
+    algebraic_notation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Player {
+    uuid: String,
+    name: String,
+    group: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Data {
+    #[serde(rename = "gameInfo")]
+    game_info: GameInfo,
+    players: Vec<Player>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Response<T> {
+    status: u32,
+    data: T,
+}
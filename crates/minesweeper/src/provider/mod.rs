@@ -0,0 +1,8 @@
+pub mod config;
+pub mod custom;
+pub mod game_id;
+pub mod greev;
+pub mod http;
+pub mod mcplayhd;
+#[allow(clippy::module_inception)]
+pub mod provider;
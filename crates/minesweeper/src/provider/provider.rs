@@ -0,0 +1,245 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::MinesweeperError;
+use crate::profile::RecentGame;
+use crate::provider::config::{ConfigKey, ConfigValues, ProviderConfig};
+use crate::provider::custom::custom_provider::CustomProvider;
+use crate::provider::game_id::{normalize_game_id, GameIdFormat};
+use crate::provider::greev::greev_provider::GreevProvider;
+use crate::provider::http::HttpClient;
+use crate::provider::mcplayhd::mcplay_provider::McPlayHdProvider;
+
+#[allow(async_fn_in_trait)]
+pub trait Provider {
+    type Config: ProviderConfig;
+
+    fn id(&self) -> &str;
+    fn name(&self) -> &str;
+    /// Secrets and variables the provider needs
+    fn config_keys(&self) -> &'static [ConfigKey] {
+        Self::Config::KEYS
+    }
+    /// Formats of the game ids the provider knows, tried in order
+    fn game_id_formats(&self, config: &Self::Config) -> Vec<GameIdFormat>;
+    /// The game id as the provider expects it, fails with [MinesweeperError::InvalidGameId]
+    /// before anything is sent upstream
+    fn normalize_game_id(
+        &self,
+        game_id: &str,
+        config: &Self::Config,
+    ) -> Result<String, MinesweeperError> {
+        normalize_game_id(&self.game_id_formats(config), game_id)
+    }
+    async fn fetch_data(
+        &self,
+        client: &impl HttpClient,
+        game_id: &str,
+        config: &Self::Config,
+    ) -> Result<ApiData, MinesweeperError>;
+    /// Name of the player, fails with [MinesweeperError::Unsupported] if the provider can't look it up
+    async fn fetch_name(
+        &self,
+        _client: &impl HttpClient,
+        _uuid: &str,
+    ) -> Result<PlayerData, MinesweeperError> {
+        Err(MinesweeperError::Unsupported)
+    }
+    /// Latest games of the player, fails with [MinesweeperError::Unsupported] if the provider can't list them
+    async fn fetch_recent_games(
+        &self,
+        _client: &impl HttpClient,
+        _uuid: &str,
+        _config: &Self::Config,
+    ) -> Result<Vec<RecentGame>, MinesweeperError> {
+        Err(MinesweeperError::Unsupported)
+    }
+}
+
+pub enum EnumProviders {
+    Greev(GreevProvider),
+    McPlayHd(McPlayHdProvider),
+    Custom(CustomProvider),
+}
+
+/// Any provider takes the raw values, which get turned into the config of the wrapped provider.
+impl ProviderConfig for ConfigValues {
+    const KEYS: &'static [ConfigKey] = &[];
+
+    fn from_values(_: &ConfigValues) -> Result<Self, MinesweeperError> {
+        Ok(ConfigValues::default())
+    }
+}
+
+impl Provider for EnumProviders {
+    type Config = ConfigValues;
+
+    fn id(&self) -> &str {
+        match self {
+            EnumProviders::Greev(provider) => provider.id(),
+            EnumProviders::McPlayHd(provider) => provider.id(),
+            EnumProviders::Custom(provider) => provider.id(),
+        }
+    }
+
+    fn name(&self) -> &str {
+        match self {
+            EnumProviders::Greev(provider) => provider.name(),
+            EnumProviders::McPlayHd(provider) => provider.name(),
+            EnumProviders::Custom(provider) => provider.name(),
+        }
+    }
+
+    fn config_keys(&self) -> &'static [ConfigKey] {
+        match self {
+            EnumProviders::Greev(provider) => provider.config_keys(),
+            EnumProviders::McPlayHd(provider) => provider.config_keys(),
+            EnumProviders::Custom(provider) => provider.config_keys(),
+        }
+    }
+
+    /// Without a working config the provider knows no formats
+    fn game_id_formats(&self, config: &ConfigValues) -> Vec<GameIdFormat> {
+        let formats =
+            match self {
+                EnumProviders::Greev(provider) => ProviderConfig::from_values(config)
+                    .map(|config| provider.game_id_formats(&config)),
+                EnumProviders::McPlayHd(provider) => ProviderConfig::from_values(config)
+                    .map(|config| provider.game_id_formats(&config)),
+                EnumProviders::Custom(provider) => ProviderConfig::from_values(config)
+                    .map(|config| provider.game_id_formats(&config)),
+            };
+
+        formats.unwrap_or_default()
+    }
+
+    fn normalize_game_id(
+        &self,
+        game_id: &str,
+        config: &ConfigValues,
+    ) -> Result<String, MinesweeperError> {
+        match self {
+            EnumProviders::Greev(provider) => {
+                let config = ProviderConfig::from_values(config)?;
+                provider.normalize_game_id(game_id, &config)
+            }
+            EnumProviders::McPlayHd(provider) => {
+                let config = ProviderConfig::from_values(config)?;
+                provider.normalize_game_id(game_id, &config)
+            }
+            EnumProviders::Custom(provider) => {
+                let config = ProviderConfig::from_values(config)?;
+                provider.normalize_game_id(game_id, &config)
+            }
+        }
+    }
+
+    async fn fetch_data(
+        &self,
+        client: &impl HttpClient,
+        game_id: &str,
+        config: &ConfigValues,
+    ) -> Result<ApiData, MinesweeperError> {
+        match self {
+            EnumProviders::Greev(provider) => {
+                let config = ProviderConfig::from_values(config)?;
+                provider.fetch_data(client, game_id, &config).await
+            }
+            EnumProviders::McPlayHd(provider) => {
+                let config = ProviderConfig::from_values(config)?;
+                provider.fetch_data(client, game_id, &config).await
+            }
+            EnumProviders::Custom(provider) => {
+                let config = ProviderConfig::from_values(config)?;
+                provider.fetch_data(client, game_id, &config).await
+            }
+        }
+    }
+
+    async fn fetch_name(
+        &self,
+        client: &impl HttpClient,
+        uuid: &str,
+    ) -> Result<PlayerData, MinesweeperError> {
+        match self {
+            EnumProviders::Greev(provider) => provider.fetch_name(client, uuid).await,
+            EnumProviders::McPlayHd(provider) => provider.fetch_name(client, uuid).await,
+            EnumProviders::Custom(provider) => provider.fetch_name(client, uuid).await,
+        }
+    }
+
+    async fn fetch_recent_games(
+        &self,
+        client: &impl HttpClient,
+        uuid: &str,
+        config: &ConfigValues,
+    ) -> Result<Vec<RecentGame>, MinesweeperError> {
+        match self {
+            EnumProviders::Greev(provider) => {
+                let config = ProviderConfig::from_values(config)?;
+                provider.fetch_recent_games(client, uuid, &config).await
+            }
+            EnumProviders::McPlayHd(provider) => {
+                let config = ProviderConfig::from_values(config)?;
+                provider.fetch_recent_games(client, uuid, &config).await
+            }
+            EnumProviders::Custom(provider) => {
+                let config = ProviderConfig::from_values(config)?;
+                provider.fetch_recent_games(client, uuid, &config).await
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ApiData {
+    #[serde(rename = "gameData")]
+    pub game_data: Option<String>,
+    #[serde(rename = "type")]
+    pub tiepe: Option<String>,
+    /// Time taken in milliseconds, if the provider tells
+    pub time: Option<u64>,
+    pub generator: Option<String>,
+    pub uuid: String,
+    #[serde(rename = "correctFlags")]
+    pub correct_flags: Option<u32>,
+    #[serde(rename = "incorrectFlags")]
+    pub incorrect_flags: Option<u32>,
+    /// Whether the game was won, if the provider tells
+    pub won: Option<bool>,
+    /// Amount of mines on the board, if the provider tells
+    pub mines: Option<u32>,
+    /// Width of the board, if the provider tells
+    pub width: Option<u32>,
+    /// Height of the board, if the provider tells
+    pub height: Option<u32>,
+    /// Unix timestamp in milliseconds of the start of the game
+    #[serde(rename = "timeStart")]
+    pub time_start: Option<u64>,
+    /// Unix timestamp in milliseconds of the end of the game
+    #[serde(rename = "timeEnd")]
+    pub time_end: Option<u64>,
+    pub players: Option<Vec<GamePlayer>>,
+}
+
+impl ApiData {
+    /// Name of the first player of the game, if the provider tells
+    pub fn player_name(&self) -> Option<&str> {
+        self.players
+            .as_ref()
+            .and_then(|players| players.first())
+            .map(|player| player.name.as_str())
+    }
+}
+
+/// Player taking part in a game
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct GamePlayer {
+    pub uuid: String,
+    pub name: String,
+    pub group: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PlayerData {
+    pub name: String,
+}
@@ -0,0 +1,959 @@
+use std::collections::{BTreeMap, HashSet};
+use std::io::Write;
+use std::time::Duration;
+
+use gif::{Encoder, Frame as GifFrame, Repeat};
+use image::imageops::{self, FilterType};
+use image::{GenericImage, ImageBuffer, Rgba};
+
+use crate::analysis::overlay::Overlay;
+use crate::analysis::replay::{losing_move, merge_moves, Move, MoveKind};
+use crate::error::MinesweeperError;
+use crate::minesweeper_logic::{Board, FieldState};
+use crate::palette::Palette;
+use crate::parsers::parser::{
+    Action, ActionType, ChordAction, FlagAction, Metadata, OpenAction, ParsedData,
+};
+use crate::text::{render_text, TextStyle};
+use crate::textures::{load_textures, ProgressColours};
+
+/// Size of a single texture in the texture sheet
+const TEXTURE_SIZE: u32 = 32;
+/// Smallest size a cell gets scaled down to on large boards
+const MIN_TILE_SIZE: u32 = 4;
+/// Longest edge of a gif, larger boards get smaller cells to keep every frame small
+pub const MAX_GIF_DIMENSION: u32 = 1024;
+/// Longest edge of a still image, larger boards get smaller cells
+pub(crate) const MAX_IMAGE_DIMENSION: u32 = 4096;
+/// Amount of cell rows rendered at once when streaming a still image into the encoder
+const STRIP_ROWS: u32 = 8;
+const PROGRESSBAR_HEIGHT: u32 = 4;
+const TIMELINE_HEIGHT: u32 = 8;
+const TIMELINE_MARKER_WIDTH: u32 = 2;
+/// Shared look of the svg shapes, the number colours follow the classic minesweeper palette
+const SVG_STYLE: &str = "<style>\
+.closed{fill:#bdbdbd;stroke:#7b7b7b}\
+.open{fill:#e0e0e0;stroke:#9e9e9e}\
+.exploded{fill:#e53935;stroke:#9e9e9e}\
+.mine{fill:#212121}\
+.flag{fill:#e53935}\
+.pole{fill:#212121}\
+text{font:bold 22px sans-serif;text-anchor:middle;dominant-baseline:central}\
+.n1{fill:#1e40ff}.n2{fill:#2e7d32}.n3{fill:#d32f2f}.n4{fill:#1a237e}\
+.n5{fill:#7f1d1d}.n6{fill:#00838f}.n7{fill:#212121}.n8{fill:#757575}.unsure{fill:#212121}\
+</style>";
+
+#[derive(Clone)]
+pub struct Renderer {
+    pub(crate) metadata: Metadata,
+    game_board: Board,
+    open_data: Vec<OpenAction>,
+    flag_data: Vec<FlagAction>,
+    chord_data: Vec<ChordAction>,
+    image_data: Imagedata,
+    tile_size: u32,
+    /// Whether the gif texture pack is used
+    gif: bool,
+    overlay: Option<Overlay>,
+    progress_style: ProgressStyle,
+    progress_colours: ProgressColours,
+    timeline: Timeline,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum RenderType {
+    #[default]
+    Image,
+    Gif,
+    /// Motion JPEG in an AVI container
+    Video,
+    /// Vector shapes with the numbers as text
+    Svg,
+    /// Plain text grid, either with ascii characters or emoji
+    Text(TextStyle),
+}
+
+impl std::str::FromStr for RenderType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_ref() {
+            "image" | "png" => Ok(RenderType::Image),
+            "gif" => Ok(RenderType::Gif),
+            "video" | "avi" => Ok(RenderType::Video),
+            "svg" => Ok(RenderType::Svg),
+            "txt" | "text" => Ok(RenderType::Text(TextStyle::Ascii)),
+            "emoji" => Ok(RenderType::Text(TextStyle::Emoji)),
+            _ => Err(format!("Unknown render type: {}", s)),
+        }
+    }
+}
+
+/// Produces the frames of an animation one at a time.
+pub trait FrameSource {
+    /// Size of every frame
+    fn dimensions(&self) -> (u32, u32);
+    /// Every colour a frame can contain
+    fn colours(&self) -> Vec<Rgba<u8>>;
+    /// Renders the next frame, pixels that did not change since the previous frame stay transparent.
+    /// Returns `None` once every frame got rendered.
+    fn next_frame(&mut self) -> Option<Result<AnimationFrame, MinesweeperError>>;
+}
+
+pub struct AnimationFrame {
+    pub image: ImageBuffer<Rgba<u8>, Vec<u8>>,
+    /// How long the frame is shown
+    pub delay: Duration,
+}
+
+/// Replays a game tick by tick, the first frame shows the untouched board.
+#[derive(Clone)]
+pub struct ReplayFrames {
+    renderer: Renderer,
+    ticks: Vec<(i64, Vec<ActionType>)>,
+    /// Amount of ticks that are applied to the board
+    applied: usize,
+    started: bool,
+}
+
+impl ReplayFrames {
+    /// The tick that gets applied next
+    pub(crate) fn next_tick(&self) -> Option<i64> {
+        self.ticks.get(self.applied).map(|(tick, _)| *tick)
+    }
+
+    pub(crate) fn apply_next_tick(&mut self) {
+        if let Some((tick, actions)) = self.ticks.get(self.applied) {
+            self.renderer.apply_tick(*tick, actions);
+            self.applied += 1;
+        }
+    }
+
+    /// Renders the changes since the last call.
+    pub(crate) fn render(&mut self) -> Result<ImageBuffer<Rgba<u8>, Vec<u8>>, MinesweeperError> {
+        let (percentage, tick) = match self.applied.checked_sub(1) {
+            None => (0, self.renderer.timeline.start),
+            Some(id) if id == self.ticks.len() - 1 => (100, self.ticks[id].0),
+            Some(id) => (
+                ((id as f32 / self.ticks.len() as f32) * 100.0) as u32,
+                self.ticks[id].0,
+            ),
+        };
+
+        self.renderer.generate_image(percentage, tick)
+    }
+
+    pub(crate) fn dimensions(&self) -> (u32, u32) {
+        self.renderer.dimensions()
+    }
+
+    pub(crate) fn colours(&self) -> Vec<Rgba<u8>> {
+        self.renderer.colours()
+    }
+
+    pub(crate) fn timeunits(&self) -> i64 {
+        self.renderer.metadata.timeunits as i64
+    }
+
+    pub(crate) fn done_percentage(&self) -> u32 {
+        self.renderer.game_board.calculate_done_percentage()
+    }
+
+    /// Whether the replay contains a click on a mine
+    pub(crate) fn lost(&self) -> bool {
+        self.renderer.timeline.losing_click.is_some()
+    }
+}
+
+impl FrameSource for ReplayFrames {
+    fn dimensions(&self) -> (u32, u32) {
+        ReplayFrames::dimensions(self)
+    }
+
+    fn colours(&self) -> Vec<Rgba<u8>> {
+        ReplayFrames::colours(self)
+    }
+
+    fn next_frame(&mut self) -> Option<Result<AnimationFrame, MinesweeperError>> {
+        let delay = if !self.started {
+            self.started = true;
+            Duration::from_secs(1)
+        } else {
+            let tick = self.next_tick()?;
+            self.apply_next_tick();
+
+            match self.next_tick() {
+                Some(next) => Duration::from_millis(((next - tick) * self.timeunits()) as u64),
+                None => Duration::from_secs(15),
+            }
+        };
+
+        Some(self.render().map(|image| AnimationFrame { image, delay }))
+    }
+}
+
+/// Encodes a gif frame by frame, every item holds the bytes encoded for one frame.
+/// The first item starts with the gif header and the last one only holds the trailer.
+pub struct GifStream {
+    source: Box<dyn FrameSource>,
+    palette: Palette,
+    /// Taken once the trailer got written or encoding failed
+    encoder: Option<Encoder<Vec<u8>>>,
+}
+
+impl GifStream {
+    pub fn new(source: impl FrameSource + 'static) -> Result<GifStream, MinesweeperError> {
+        let (width, height) = source.dimensions();
+        let palette = Palette::new(source.colours());
+
+        let mut encoder = Encoder::new(Vec::new(), width as u16, height as u16, palette.rgb())
+            .map_err(|_| MinesweeperError::GifEncoding)?;
+        encoder
+            .set_repeat(Repeat::Infinite)
+            .map_err(|_| MinesweeperError::GifEncoding)?;
+
+        Ok(GifStream {
+            source: Box::new(source),
+            palette,
+            encoder: Some(encoder),
+        })
+    }
+
+    fn write_frame(
+        &self,
+        encoder: &mut Encoder<Vec<u8>>,
+        animation_frame: AnimationFrame,
+    ) -> Result<(), MinesweeperError> {
+        let (width, height) = animation_frame.image.dimensions();
+
+        let mut frame = GifFrame::from_indexed_pixels(
+            width as u16,
+            height as u16,
+            self.palette.index_frame(&animation_frame.image),
+            Some(self.palette.transparent()),
+        );
+        frame.delay = (animation_frame.delay.as_millis() / 10).min(u16::MAX as u128) as u16;
+        frame.dispose = gif::DisposalMethod::Keep;
+
+        encoder
+            .write_frame(&frame)
+            .map_err(|_| MinesweeperError::GifEncoding)
+    }
+}
+
+impl Iterator for GifStream {
+    type Item = Result<Vec<u8>, MinesweeperError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut encoder = self.encoder.take()?;
+
+        let Some(animation_frame) = self.source.next_frame() else {
+            return Some(
+                encoder
+                    .into_inner()
+                    .map_err(|_| MinesweeperError::GifEncoding),
+            );
+        };
+
+        let result = animation_frame
+            .and_then(|animation_frame| self.write_frame(&mut encoder, animation_frame));
+        let bytes = std::mem::take(encoder.get_mut());
+        if result.is_ok() {
+            self.encoder = Some(encoder);
+        }
+
+        Some(result.map(|_| bytes))
+    }
+}
+
+/// What the indicator below the board shows.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ProgressStyle {
+    /// Share of replayed ticks in a gif, share of opened safe cells in a still image
+    #[default]
+    Ticks,
+    /// Share of safe cells that are open
+    Cells,
+    /// Elapsed game time
+    Time,
+    /// Elapsed game time on a strip with markers for placed flags and the losing click
+    Timeline,
+}
+
+impl ProgressStyle {
+    fn height(&self) -> u32 {
+        match self {
+            ProgressStyle::Timeline => TIMELINE_HEIGHT,
+            _ => PROGRESSBAR_HEIGHT,
+        }
+    }
+}
+
+impl std::str::FromStr for ProgressStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_ref() {
+            "ticks" => Ok(ProgressStyle::Ticks),
+            "cells" => Ok(ProgressStyle::Cells),
+            "time" => Ok(ProgressStyle::Time),
+            "timeline" => Ok(ProgressStyle::Timeline),
+            _ => Err(format!("Unknown progress style: {}", s)),
+        }
+    }
+}
+
+/// Timing of the replay, collected before rendering consumes the actions.
+#[derive(Clone)]
+struct Timeline {
+    start: i64,
+    end: i64,
+    flags: Vec<i64>,
+    losing_click: Option<i64>,
+}
+
+impl Timeline {
+    fn new(board: &Board, moves: &[Move]) -> Timeline {
+        Timeline {
+            start: moves.first().map_or(0, |mv| mv.total_time),
+            end: moves.last().map_or(0, |mv| mv.total_time),
+            flags: moves
+                .iter()
+                .filter(|mv| matches!(mv.kind, MoveKind::Flag(Action::Place)))
+                .map(|mv| mv.total_time)
+                .collect(),
+            losing_click: losing_move(board, moves).map(|mv| mv.total_time),
+        }
+    }
+
+    fn elapsed_percentage(&self, tick: i64) -> u32 {
+        if self.end <= self.start {
+            return 100;
+        }
+
+        ((tick - self.start).clamp(0, self.end - self.start) * 100 / (self.end - self.start)) as u32
+    }
+
+    /// Pixel column of the tick on a strip of the given width
+    fn position(&self, tick: i64, width: u32) -> u32 {
+        if self.end <= self.start {
+            return 0;
+        }
+
+        ((tick - self.start) * (width.saturating_sub(1)) as i64 / (self.end - self.start)) as u32
+    }
+}
+
+#[derive(Clone)]
+struct Imagedata {
+    zero: ImageBuffer<Rgba<u8>, Vec<u8>>,
+    one: ImageBuffer<Rgba<u8>, Vec<u8>>,
+    two: ImageBuffer<Rgba<u8>, Vec<u8>>,
+    three: ImageBuffer<Rgba<u8>, Vec<u8>>,
+    four: ImageBuffer<Rgba<u8>, Vec<u8>>,
+    five: ImageBuffer<Rgba<u8>, Vec<u8>>,
+    six: ImageBuffer<Rgba<u8>, Vec<u8>>,
+    seven: ImageBuffer<Rgba<u8>, Vec<u8>>,
+    eight: ImageBuffer<Rgba<u8>, Vec<u8>>,
+    tnt: ImageBuffer<Rgba<u8>, Vec<u8>>,
+    empty: ImageBuffer<Rgba<u8>, Vec<u8>>,
+    flag: ImageBuffer<Rgba<u8>, Vec<u8>>,
+    unsure_flag: ImageBuffer<Rgba<u8>, Vec<u8>>,
+}
+
+impl Imagedata {
+    fn textures(&self) -> [&ImageBuffer<Rgba<u8>, Vec<u8>>; 13] {
+        [
+            &self.zero,
+            &self.one,
+            &self.two,
+            &self.three,
+            &self.four,
+            &self.five,
+            &self.six,
+            &self.seven,
+            &self.eight,
+            &self.tnt,
+            &self.empty,
+            &self.flag,
+            &self.unsure_flag,
+        ]
+    }
+
+    pub fn new(sprite_data: &[u8], tile_size: u32) -> Imagedata {
+        let im = &mut image::load_from_memory(sprite_data).expect("Custom Textures file not found");
+
+        let mut texture = |index: u32| {
+            let texture = im.sub_image(TEXTURE_SIZE * index, 0, TEXTURE_SIZE, TEXTURE_SIZE);
+            if tile_size == TEXTURE_SIZE {
+                texture.to_image()
+            } else {
+                imageops::resize(&*texture, tile_size, tile_size, FilterType::Triangle)
+            }
+        };
+
+        Imagedata {
+            zero: texture(0),
+            one: texture(1),
+            two: texture(2),
+            three: texture(3),
+            four: texture(4),
+            five: texture(5),
+            six: texture(6),
+            seven: texture(7),
+            eight: texture(8),
+            tnt: texture(9),
+            empty: texture(10),
+            flag: texture(11),
+            unsure_flag: texture(12),
+        }
+    }
+}
+
+impl Renderer {
+    pub fn new(
+        metadata: Metadata,
+        game_board: Board,
+        open_data: Vec<OpenAction>,
+        flag_data: Vec<FlagAction>,
+        chord_data: Vec<ChordAction>,
+        gif: &bool,
+    ) -> Renderer {
+        let max_dimension = if *gif {
+            MAX_GIF_DIMENSION
+        } else {
+            MAX_IMAGE_DIMENSION
+        };
+        let tile_size = tile_size(&metadata, max_dimension);
+        let textures = load_textures(gif);
+        let timeline = Timeline::new(
+            &game_board,
+            &merge_moves(&open_data, &flag_data, &chord_data),
+        );
+
+        Renderer {
+            metadata,
+            game_board,
+            open_data,
+            flag_data,
+            chord_data,
+            image_data: Imagedata::new(textures.sprites.as_slice(), tile_size),
+            tile_size,
+            gif: *gif,
+            overlay: None,
+            progress_style: ProgressStyle::default(),
+            progress_colours: textures.progress,
+            timeline,
+        }
+    }
+
+    /// Renders a parsed replay, `gif` selects the textures and size limit of animations.
+    pub fn from_parsed(parsed_data: ParsedData, gif: bool) -> Renderer {
+        Renderer::new(
+            parsed_data.metadata,
+            parsed_data.game_board,
+            parsed_data.open_data,
+            parsed_data.flag_data,
+            parsed_data.chord_data,
+            &gif,
+        )
+    }
+
+    /// Shrinks the cells so the longest side of the board stays within the given amount of pixels.
+    pub fn with_max_dimension(mut self, max_dimension: u32) -> Renderer {
+        let tile_size = tile_size(&self.metadata, max_dimension).min(self.tile_size);
+        if tile_size != self.tile_size {
+            self.tile_size = tile_size;
+            self.image_data =
+                Imagedata::new(load_textures(&self.gif).sprites.as_slice(), tile_size);
+        }
+        self
+    }
+
+    /// Changes what the indicator below the board shows.
+    pub fn with_progress_style(mut self, progress_style: ProgressStyle) -> Renderer {
+        self.progress_style = progress_style;
+        self
+    }
+
+    /// Tints the cells with the given colours on top of their textures.
+    pub fn with_overlay(mut self, overlay: Overlay) -> Renderer {
+        self.overlay = Some(overlay);
+        self
+    }
+
+    pub fn render_jpeg(&mut self) -> Result<Vec<u8>, MinesweeperError> {
+        self.apply_all_actions();
+
+        let percentage_done = self.game_board.calculate_done_percentage();
+        let (width, height) = self.dimensions();
+
+        let mut buffer = Vec::new();
+        let mut encoder = png::Encoder::new(&mut buffer, width, height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder
+            .write_header()
+            .map_err(|_| MinesweeperError::PngEncoding)?;
+        // The image is rendered in strips of rows, so large boards never need the whole image in memory
+        let mut stream = writer
+            .stream_writer()
+            .map_err(|_| MinesweeperError::PngEncoding)?;
+
+        for first_row in (0..self.metadata.y_size as u32).step_by(STRIP_ROWS as usize) {
+            let rows = STRIP_ROWS.min(self.metadata.y_size as u32 - first_row);
+            let mut strip = ImageBuffer::new(width, rows * self.tile_size);
+
+            for y in first_row..first_row + rows {
+                for x in 0..self.metadata.x_size as u32 {
+                    self.draw_cell(&mut strip, x, y, (y - first_row) * self.tile_size)?;
+                }
+            }
+
+            stream
+                .write_all(strip.as_raw())
+                .map_err(|_| MinesweeperError::PngEncoding)?;
+        }
+
+        let mut progressbar = ImageBuffer::new(width, self.progress_style.height());
+        self.draw_progress(&mut progressbar, 0, percentage_done, self.timeline.end);
+        stream
+            .write_all(progressbar.as_raw())
+            .map_err(|_| MinesweeperError::PngEncoding)?;
+
+        stream.finish().map_err(|_| MinesweeperError::PngEncoding)?;
+        writer.finish().map_err(|_| MinesweeperError::PngEncoding)?;
+
+        Ok(buffer)
+    }
+
+    /// Renders the final board as vector shapes, every cell being `TEXTURE_SIZE` units wide
+    /// no matter how large the board is.
+    pub fn render_svg(&mut self) -> String {
+        self.apply_all_actions();
+
+        let percentage_done = self.game_board.calculate_done_percentage();
+        let width = self.metadata.x_size as u32 * TEXTURE_SIZE;
+        let board_height = self.metadata.y_size as u32 * TEXTURE_SIZE;
+        let height = board_height + self.progress_style.height();
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\" role=\"img\">\n"
+        );
+        svg.push_str(&format!(
+            "<title>Minesweeper board {}x{}, {}% done</title>\n",
+            self.metadata.x_size, self.metadata.y_size, percentage_done
+        ));
+        svg.push_str(SVG_STYLE);
+        svg.push('\n');
+
+        for y in 0..self.metadata.y_size as u32 {
+            for x in 0..self.metadata.x_size as u32 {
+                self.svg_cell(&mut svg, x, y);
+            }
+        }
+
+        let percentage = self.progress_percentage(percentage_done, self.timeline.end);
+        let done_width = (percentage * width) / 100;
+        let progress_height = self.progress_style.height();
+        svg.push_str(&format!(
+            "<rect x=\"0\" y=\"{board_height}\" width=\"{width}\" height=\"{progress_height}\" fill=\"{}\"/>\n",
+            hex(self.progress_colours.left)
+        ));
+        svg.push_str(&format!(
+            "<rect x=\"0\" y=\"{board_height}\" width=\"{done_width}\" height=\"{progress_height}\" fill=\"{}\"/>\n",
+            hex(self.progress_colours.done)
+        ));
+        for (marker, colour) in self.timeline_markers() {
+            svg.push_str(&format!(
+                "<rect x=\"{}\" y=\"{board_height}\" width=\"{TIMELINE_MARKER_WIDTH}\" height=\"{progress_height}\" fill=\"{}\"/>\n",
+                self.timeline.position(marker, width),
+                hex(colour)
+            ));
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Writes the final board as a text grid.
+    pub fn render_text(&mut self, style: TextStyle) -> String {
+        self.apply_all_actions();
+        render_text(&self.game_board, style)
+    }
+
+    /// Turns the renderer into an iterator which renders and encodes one frame per item,
+    /// so only a single frame is ever held in memory.
+    pub fn into_gif_stream(self) -> Result<GifStream, MinesweeperError> {
+        GifStream::new(self.into_frames())
+    }
+
+    /// Turns the renderer into a source of animation frames, one per tick.
+    pub fn into_frames(mut self) -> ReplayFrames {
+        let ticks = self.create_tick_map().into_iter().collect();
+
+        ReplayFrames {
+            renderer: self,
+            ticks,
+            applied: 0,
+            started: false,
+        }
+    }
+
+    /// Size of a rendered image including the progress indicator
+    pub fn dimensions(&self) -> (u32, u32) {
+        (
+            self.metadata.x_size as u32 * self.tile_size,
+            self.metadata.y_size as u32 * self.tile_size + self.progress_style.height(),
+        )
+    }
+
+    /// Every colour an image can contain: the textures, the progress bar and the textures tinted by the overlay.
+    fn colours(&self) -> Vec<Rgba<u8>> {
+        let texture_colours: HashSet<Rgba<u8>> = self
+            .image_data
+            .textures()
+            .into_iter()
+            .flat_map(|texture| texture.pixels().copied())
+            .collect();
+        let tints: HashSet<Rgba<u8>> = self
+            .overlay
+            .iter()
+            .flatten()
+            .flatten()
+            .flatten()
+            .copied()
+            .collect();
+
+        let tinted = tints.iter().flat_map(|tint| {
+            texture_colours
+                .iter()
+                .map(move |colour| blend(*colour, *tint))
+        });
+
+        texture_colours
+            .iter()
+            .copied()
+            .chain(tinted)
+            .chain(self.progress_colours.all())
+            .collect()
+    }
+
+    /// Applies every action to the board in the order they were played, for renderings of the final state.
+    fn apply_all_actions(&mut self) {
+        merge_moves(&self.open_data, &self.flag_data, &self.chord_data)
+            .iter()
+            .for_each(|mv| mv.apply(&mut self.game_board));
+    }
+
+    /// Applies every action of the given tick to the board.
+    fn apply_tick(&mut self, tick: i64, actions: &[ActionType]) {
+        if actions.contains(&ActionType::Flag) {
+            self.flag_data
+                .iter()
+                .filter(|flag| flag.total_time == tick)
+                .for_each(|flag| flag.perform_action(&mut self.game_board));
+            //Remove all elements which are less than tick
+            self.flag_data.retain(|flag| flag.total_time > tick)
+        }
+
+        if actions.contains(&ActionType::Open) {
+            self.open_data
+                .iter()
+                .filter(|open| open.total_time == tick)
+                .for_each(|action| {
+                    self.game_board
+                        .open_field(action.x as usize, action.y as usize);
+                });
+
+            //Remove all elements which are less than tick
+            self.open_data.retain(|open| open.total_time > tick)
+        }
+
+        if actions.contains(&ActionType::Chord) {
+            self.chord_data
+                .iter()
+                .filter(|chord| chord.total_time == tick)
+                .for_each(|action| {
+                    self.game_board
+                        .chord_field(action.x as usize, action.y as usize);
+                });
+
+            //Remove all elements which are less than tick
+            self.chord_data.retain(|chord| chord.total_time > tick)
+        }
+    }
+
+    fn create_tick_map(&mut self) -> BTreeMap<i64, Vec<ActionType>> {
+        let mut tick_map = BTreeMap::new();
+
+        for x in self.open_data.iter() {
+            Self::insert_action(&mut tick_map, x.total_time, ActionType::Open)
+        }
+
+        for x in self.flag_data.iter() {
+            Self::insert_action(&mut tick_map, x.total_time, ActionType::Flag)
+        }
+
+        for x in self.chord_data.iter() {
+            Self::insert_action(&mut tick_map, x.total_time, ActionType::Chord)
+        }
+
+        tick_map
+    }
+
+    fn insert_action(
+        tick_map: &mut BTreeMap<i64, Vec<ActionType>>,
+        total_time: i64,
+        action: ActionType,
+    ) {
+        if let std::collections::btree_map::Entry::Vacant(e) = tick_map.entry(total_time) {
+            e.insert(vec![action]);
+        } else {
+            tick_map.get_mut(&total_time).unwrap().push(action);
+        }
+    }
+
+    /// Renders the changed cells and the progress indicator, `tick_percentage` being the share of replayed ticks.
+    fn generate_image(
+        &mut self,
+        tick_percentage: u32,
+        tick: i64,
+    ) -> Result<ImageBuffer<Rgba<u8>, Vec<u8>>, MinesweeperError> {
+        let (imgx, imgy) = self.dimensions();
+
+        let mut imgbuf = image::ImageBuffer::new(imgx, imgy);
+
+        for x in 0..self.metadata.x_size as u32 {
+            for y in 0..self.metadata.y_size as u32 {
+                // Only render fields that got changed in the last iteration
+                if !self.game_board.changed_fields[y as usize][x as usize] {
+                    continue;
+                }
+
+                self.draw_cell(&mut imgbuf, x, y, y * self.tile_size)?;
+            }
+        }
+
+        self.draw_progress(
+            &mut imgbuf,
+            imgy - self.progress_style.height(),
+            tick_percentage,
+            tick,
+        );
+
+        //Reset the changed fields after they got rendered
+        self.game_board
+            .changed_fields
+            .iter_mut()
+            .for_each(|row| row.iter_mut().for_each(|field| *field = false));
+
+        Ok(imgbuf)
+    }
+
+    /// Draws the progress indicator over the full width, starting at the pixel row `top`.
+    fn draw_progress(
+        &self,
+        imgbuf: &mut ImageBuffer<Rgba<u8>, Vec<u8>>,
+        top: u32,
+        tick_percentage: u32,
+        tick: i64,
+    ) {
+        let imgx = imgbuf.width();
+        let bottom = top + self.progress_style.height();
+        let colours = &self.progress_colours;
+
+        let percentage = self.progress_percentage(tick_percentage, tick);
+        let pixel_coloring = (percentage * imgx) / 100;
+
+        for x in 0..imgx {
+            for y in top..bottom {
+                let pixel = imgbuf.get_pixel_mut(x, y);
+                if x <= pixel_coloring {
+                    *pixel = colours.done;
+                } else {
+                    *pixel = colours.left;
+                }
+            }
+        }
+
+        for (marker, colour) in self.timeline_markers() {
+            let xx = self.timeline.position(marker, imgx);
+            for x in xx..(xx + TIMELINE_MARKER_WIDTH).min(imgx) {
+                for y in top..bottom {
+                    imgbuf.put_pixel(x, y, colour);
+                }
+            }
+        }
+    }
+
+    /// Share of the progress indicator that is filled for the current progress style.
+    fn progress_percentage(&self, tick_percentage: u32, tick: i64) -> u32 {
+        match self.progress_style {
+            ProgressStyle::Ticks => tick_percentage,
+            ProgressStyle::Cells => self.game_board.calculate_done_percentage(),
+            ProgressStyle::Time | ProgressStyle::Timeline => self.timeline.elapsed_percentage(tick),
+        }
+    }
+
+    /// Ticks and colours of the markers on the timeline, empty for every other progress style.
+    fn timeline_markers(&self) -> Vec<(i64, Rgba<u8>)> {
+        if self.progress_style != ProgressStyle::Timeline {
+            return Vec::new();
+        }
+
+        self.timeline
+            .flags
+            .iter()
+            .map(|flag| (*flag, self.progress_colours.flag))
+            .chain(
+                self.timeline
+                    .losing_click
+                    .map(|click| (click, self.progress_colours.mine)),
+            )
+            .collect()
+    }
+
+    /// Appends the shapes of the cell at `x`/`y` of the board to the svg.
+    fn svg_cell(&self, svg: &mut String, x: u32, y: u32) {
+        let field = &self.game_board.fields[y as usize][x as usize];
+        let (xx, yy) = (x * TEXTURE_SIZE, y * TEXTURE_SIZE);
+        let (cx, cy) = (xx + TEXTURE_SIZE / 2, yy + TEXTURE_SIZE / 2);
+        let rect = |class: &str| {
+            format!(
+                "<rect class=\"{class}\" x=\"{xx}\" y=\"{yy}\" width=\"{TEXTURE_SIZE}\" height=\"{TEXTURE_SIZE}\"/>"
+            )
+        };
+
+        let shapes = match field.field_state {
+            FieldState::Closed => rect("closed"),
+            FieldState::Flagged => format!(
+                "{}<rect class=\"pole\" x=\"{}\" y=\"{}\" width=\"2\" height=\"20\"/><polygon class=\"flag\" points=\"{},{} {},{} {},{}\"/>",
+                rect("closed"),
+                cx + 2,
+                yy + 6,
+                cx + 2,
+                yy + 6,
+                cx + 2,
+                yy + 16,
+                cx - 8,
+                yy + 11
+            ),
+            FieldState::UnsureFlagged => format!(
+                "{}<text class=\"unsure\" x=\"{cx}\" y=\"{cy}\">?</text>",
+                rect("closed")
+            ),
+            FieldState::Open if field.mine => format!(
+                "{}<circle class=\"mine\" cx=\"{cx}\" cy=\"{cy}\" r=\"9\"/>",
+                rect("exploded")
+            ),
+            FieldState::Open if field.value == 0 => rect("open"),
+            FieldState::Open => format!(
+                "{}<text class=\"n{}\" x=\"{cx}\" y=\"{cy}\">{}</text>",
+                rect("open"),
+                field.value,
+                field.value
+            ),
+        };
+        svg.push_str(&shapes);
+
+        // Closed cells stay untinted so the overlay does not give away upcoming moves
+        if let Some(tint) = self
+            .overlay
+            .as_ref()
+            .filter(|_| field.field_state != FieldState::Closed)
+            .and_then(|overlay| overlay[y as usize][x as usize])
+        {
+            svg.push_str(&format!(
+                "<rect x=\"{xx}\" y=\"{yy}\" width=\"{TEXTURE_SIZE}\" height=\"{TEXTURE_SIZE}\" fill=\"{}\" fill-opacity=\"{:.2}\"/>",
+                hex(tint),
+                tint.0[3] as f32 / 255_f32
+            ));
+        }
+        svg.push('\n');
+    }
+
+    /// Draws the cell at `x`/`y` of the board into the buffer, `yy` being the pixel row inside the buffer.
+    fn draw_cell(
+        &self,
+        imgbuf: &mut ImageBuffer<Rgba<u8>, Vec<u8>>,
+        x: u32,
+        y: u32,
+        yy: u32,
+    ) -> Result<(), MinesweeperError> {
+        let field = &self.game_board.fields[y as usize][x as usize];
+        let xx = x * self.tile_size;
+
+        let texture = match field.field_state {
+            FieldState::Closed => &self.image_data.empty,
+            FieldState::Flagged => &self.image_data.flag,
+            FieldState::UnsureFlagged => &self.image_data.unsure_flag,
+            FieldState::Open if field.mine => &self.image_data.tnt,
+            FieldState::Open => match field.value {
+                0 => &self.image_data.zero,
+                1 => &self.image_data.one,
+                2 => &self.image_data.two,
+                3 => &self.image_data.three,
+                4 => &self.image_data.four,
+                5 => &self.image_data.five,
+                6 => &self.image_data.six,
+                7 => &self.image_data.seven,
+                8 => &self.image_data.eight,
+                _ => unreachable!(),
+            },
+        };
+
+        imgbuf
+            .copy_from(texture, xx, yy)
+            .map_err(|_| MinesweeperError::ImageInsertion)?;
+
+        // Closed cells stay untinted so the overlay does not give away upcoming moves
+        if let Some(tint) = self
+            .overlay
+            .as_ref()
+            .filter(|_| field.field_state != FieldState::Closed)
+            .and_then(|overlay| overlay[y as usize][x as usize])
+        {
+            tint_cell(imgbuf, xx, yy, self.tile_size, tint);
+        }
+
+        Ok(())
+    }
+}
+
+/// Largest cell size that keeps the longest side of the board within the given amount of pixels.
+fn tile_size(metadata: &Metadata, max_dimension: u32) -> u32 {
+    let longest_side = metadata.x_size.max(metadata.y_size).max(1) as u32;
+    (max_dimension / longest_side).clamp(MIN_TILE_SIZE, TEXTURE_SIZE)
+}
+
+/// Alpha blends the colour over a single cell.
+fn tint_cell(
+    imgbuf: &mut ImageBuffer<Rgba<u8>, Vec<u8>>,
+    xx: u32,
+    yy: u32,
+    size: u32,
+    tint: Rgba<u8>,
+) {
+    for x in xx..xx + size {
+        for y in yy..yy + size {
+            let pixel = imgbuf.get_pixel_mut(x, y);
+            *pixel = blend(*pixel, tint);
+        }
+    }
+}
+
+/// Colour as a hex code for svg attributes, ignoring the alpha channel
+fn hex(colour: Rgba<u8>) -> String {
+    format!("#{:02x}{:02x}{:02x}", colour.0[0], colour.0[1], colour.0[2])
+}
+
+fn blend(pixel: Rgba<u8>, tint: Rgba<u8>) -> Rgba<u8> {
+    let alpha = tint.0[3] as u32;
+    let mix = |channel: usize| {
+        ((pixel.0[channel] as u32 * (255 - alpha) + tint.0[channel] as u32 * alpha) / 255) as u8
+    };
+
+    Rgba([mix(0), mix(1), mix(2), pixel.0[3]])
+}
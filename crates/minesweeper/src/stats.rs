@@ -0,0 +1,208 @@
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::analysis::replay::{merge_moves, MoveKind};
+use crate::minesweeper_logic::FieldState;
+use crate::parsers::parser::{Action, Generator, ParsedData};
+use crate::provider::provider::{ApiData, GamePlayer};
+
+/// Milliseconds the reported time may differ from the last action of the replay
+const TIME_TOLERANCE: u64 = 1000;
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GameStats {
+    /// Values as reported by the provider
+    pub reported: ReportedStats,
+    /// Values reconstructed from the replay
+    pub replay: ReplayStats,
+    /// Reported values the replay disagrees with
+    pub mismatches: Vec<Mismatch>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportedStats {
+    pub uuid: String,
+    pub time: Option<u64>,
+    pub won: Option<bool>,
+    pub correct_flags: Option<u32>,
+    pub incorrect_flags: Option<u32>,
+    pub generator: Option<Generator>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mines: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_start: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_end: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub players: Option<Vec<GamePlayer>>,
+}
+
+/// Value reported by the provider which does not match the replay
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Mismatch {
+    pub field: &'static str,
+    pub reported: Value,
+    pub replay: Value,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayStats {
+    pub width: i32,
+    pub height: i32,
+    pub mines: u32,
+    pub opens: usize,
+    pub chords: usize,
+    pub flags_placed: usize,
+    pub flags_removed: usize,
+    pub flags_toggled: usize,
+    /// Time of the last action in milliseconds
+    pub duration: i64,
+    pub opened_fields: u32,
+    pub done_percentage: u32,
+    pub won: bool,
+    /// Flags left on mines at the end of the game
+    pub correct_flags: u32,
+    /// Flags left on safe fields at the end of the game
+    pub incorrect_flags: u32,
+}
+
+impl GameStats {
+    pub fn new(api_data: &ApiData, parsed_data: &ParsedData) -> GameStats {
+        let replay = ReplayStats::new(parsed_data);
+
+        GameStats {
+            reported: ReportedStats {
+                uuid: api_data.uuid.clone(),
+                time: api_data.time,
+                won: api_data.won,
+                correct_flags: api_data.correct_flags,
+                incorrect_flags: api_data.incorrect_flags,
+                generator: parsed_data.metadata.generator.clone(),
+                mines: api_data.mines,
+                width: api_data.width,
+                height: api_data.height,
+                time_start: api_data.time_start,
+                time_end: api_data.time_end,
+                players: api_data.players.clone(),
+            },
+            mismatches: mismatches(api_data, &replay),
+            replay,
+        }
+    }
+}
+
+/// Compares the values reported by the provider against the replay, values the provider
+/// left out are not checked.
+pub fn mismatches(api_data: &ApiData, replay: &ReplayStats) -> Vec<Mismatch> {
+    let checks: [(&'static str, Option<Value>, Value); 6] = [
+        (
+            "mines",
+            api_data.mines.map(Value::from),
+            replay.mines.into(),
+        ),
+        (
+            "width",
+            api_data.width.map(Value::from),
+            replay.width.into(),
+        ),
+        (
+            "height",
+            api_data.height.map(Value::from),
+            replay.height.into(),
+        ),
+        (
+            "correctFlags",
+            api_data.correct_flags.map(Value::from),
+            replay.correct_flags.into(),
+        ),
+        (
+            "incorrectFlags",
+            api_data.incorrect_flags.map(Value::from),
+            replay.incorrect_flags.into(),
+        ),
+        ("won", api_data.won.map(Value::from), replay.won.into()),
+    ];
+
+    let mut mismatches: Vec<Mismatch> = checks
+        .into_iter()
+        .filter_map(|(field, reported, replay)| match reported {
+            Some(reported) if reported != replay => Some(Mismatch {
+                field,
+                reported,
+                replay,
+            }),
+            _ => None,
+        })
+        .collect();
+
+    // The clock of the provider keeps running a bit after the last action of the replay
+    if let Some(time) = api_data
+        .time
+        .filter(|time| time.abs_diff(replay.duration.max(0) as u64) > TIME_TOLERANCE)
+    {
+        mismatches.push(Mismatch {
+            field: "time",
+            reported: time.into(),
+            replay: replay.duration.into(),
+        });
+    }
+
+    mismatches
+}
+
+impl ReplayStats {
+    pub fn new(parsed_data: &ParsedData) -> ReplayStats {
+        let moves = merge_moves(
+            &parsed_data.open_data,
+            &parsed_data.flag_data,
+            &parsed_data.chord_data,
+        );
+
+        let mut board = parsed_data.game_board.clone();
+        moves.iter().for_each(|mv| mv.apply(&mut board));
+
+        let count =
+            |matches: fn(&MoveKind) -> bool| moves.iter().filter(|mv| matches(&mv.kind)).count();
+
+        let flags = |mine: bool| {
+            board
+                .fields
+                .iter()
+                .flatten()
+                .filter(|field| field.field_state == FieldState::Flagged && field.mine == mine)
+                .count() as u32
+        };
+
+        let lost = board
+            .fields
+            .iter()
+            .flatten()
+            .any(|field| field.mine && field.field_state == FieldState::Open);
+
+        ReplayStats {
+            width: parsed_data.metadata.x_size,
+            height: parsed_data.metadata.y_size,
+            mines: board.mine_count,
+            opens: count(|kind| matches!(kind, MoveKind::Open)),
+            chords: count(|kind| matches!(kind, MoveKind::Chord)),
+            flags_placed: count(|kind| matches!(kind, MoveKind::Flag(Action::Place))),
+            flags_removed: count(|kind| matches!(kind, MoveKind::Flag(Action::Remove))),
+            flags_toggled: count(|kind| matches!(kind, MoveKind::Flag(Action::Toggle))),
+            duration: moves.last().map_or(0, |mv| mv.total_time)
+                * parsed_data.metadata.timeunits as i64,
+            opened_fields: board.open_fields,
+            done_percentage: board.calculate_done_percentage(),
+            won: !lost && board.open_fields == board.total_fields - board.mine_count,
+            correct_flags: flags(true),
+            incorrect_flags: flags(false),
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use crate::analysis::anticheat::{analyse, FirstClick, Heuristic};
+use crate::tests::{fixture, flag, open, replay};
+
+const LARGE_V2: &str = include_str!("fixtures/large_v2.txt");
+const REPLAY_V1_9X9: &str = include_str!("fixtures/replay_v1_9x9.txt");
+const REPLAY_V2_16X16: &str = include_str!("fixtures/replay_v2_16x16.txt");
+
+/// Five isolated 50/50 pairs stacked on top of each other, the mine is always on the left.
+/// With `safe_cell` a closed cell below them is provably safe the whole game.
+fn fifty_fifty_pairs(safe_cell: bool) -> Vec<&'static str> {
+    let mut rows = vec!["*."];
+    for _ in 0..4 {
+        rows.extend(["oo", "oo", "*."]);
+    }
+    if safe_cell {
+        rows.extend(["oo", "o."]);
+    }
+    rows
+}
+
+fn guessed_right_sides(board: &[&str]) -> Vec<Heuristic> {
+    // The first click is not counted as a guess, leaving four survived 50/50s
+    let moves: Vec<_> = (0..5).map(|i| open(1, 3 * i, 10 * i as i64)).collect();
+    analyse(&replay(board, &moves))
+        .findings
+        .iter()
+        .map(|finding| finding.heuristic)
+        .collect()
+}
+
+#[test]
+fn human_replays_are_clean() {
+    for game_data in [REPLAY_V1_9X9, LARGE_V2] {
+        let report = analyse(&fixture(game_data));
+
+        assert_eq!(report.score, 0, "{:?}", report.findings);
+        assert!(report.findings.is_empty());
+        assert_eq!(report.first_click, FirstClick::Safe);
+    }
+}
+
+#[test]
+fn surviving_forced_guesses_is_reported() {
+    let findings = guessed_right_sides(&fifty_fifty_pairs(false));
+
+    assert_eq!(findings, [Heuristic::ForcedGuessLuck]);
+}
+
+#[test]
+fn guessing_next_to_a_safe_cell_is_reported() {
+    let findings = guessed_right_sides(&fifty_fifty_pairs(true));
+
+    assert_eq!(findings, [Heuristic::UndeducibleOpens]);
+}
+
+#[test]
+fn moves_within_one_tick_are_reported() {
+    let mut parsed_data = fixture(REPLAY_V2_16X16);
+    parsed_data
+        .open_data
+        .iter_mut()
+        .for_each(|open| open.total_time = 0);
+
+    let report = analyse(&parsed_data);
+    let finding = report
+        .findings
+        .iter()
+        .find(|finding| finding.heuristic == Heuristic::ZeroTimeReactions)
+        .expect("zero time moves are reported");
+    assert!(finding.evidence.len() + 1 >= parsed_data.open_data.len());
+    assert!(report.score > 0);
+}
+
+#[test]
+fn fast_and_perfect_flags_are_reported() {
+    // Every number only sees the mine to its right
+    let row = "o*".repeat(12);
+    let flags: Vec<_> = (0..12).map(|i| flag(2 * i + 1, 0, i as i64)).collect();
+
+    let report = analyse(&replay(&[&row], &flags));
+
+    assert_eq!(report.findings.len(), 1);
+    assert_eq!(report.findings[0].heuristic, Heuristic::FlagAccuracySpeed);
+    assert_eq!(report.findings[0].evidence.len(), 12);
+    assert_eq!(report.first_click, FirstClick::NotPlayed);
+}
@@ -0,0 +1,135 @@
+use std::io::Cursor;
+
+use image::codecs::gif::GifDecoder;
+use image::AnimationDecoder;
+
+use crate::analysis::replay::{Move, MoveKind};
+use crate::error::MinesweeperError;
+use crate::minesweeper_logic::{Board, FieldState};
+use crate::parsers::parser::parse_game_data;
+use crate::renderer::Renderer;
+use crate::tests::{board, flag, open, replay};
+use crate::text::TextStyle;
+
+fn chord(x: usize, y: usize, total_time: i64) -> Move {
+    Move {
+        kind: MoveKind::Chord,
+        ..open(x, y, total_time)
+    }
+}
+
+fn states(board: &Board) -> Vec<Vec<FieldState>> {
+    board
+        .fields
+        .iter()
+        .map(|row| row.iter().map(|field| field.field_state.clone()).collect())
+        .collect()
+}
+
+#[test]
+fn chord_opens_the_unflagged_neighbours() {
+    let mut board = board(&["*..", ".o.", "..."]);
+    board.fields[0][0].field_state = FieldState::Flagged;
+
+    board.chord_field(1, 1);
+
+    let mut expected = vec![vec![FieldState::Open; 3]; 3];
+    expected[0][0] = FieldState::Flagged;
+    assert_eq!(states(&board), expected);
+}
+
+#[test]
+fn chord_without_enough_flags_does_nothing() {
+    let mut board = board(&["*..", ".o.", "..*"]);
+    board.fields[0][0].field_state = FieldState::Flagged;
+    let before = states(&board);
+
+    assert!(board.chord_targets(1, 1).is_empty());
+    board.chord_field(1, 1);
+    assert_eq!(states(&board), before);
+
+    // Chords on closed cells and on zeros are no-ops as well
+    assert!(board.chord_targets(2, 0).is_empty());
+}
+
+#[test]
+fn chord_next_to_a_wrong_flag_opens_the_mine() {
+    let mut board = board(&["*..", ".o.", "..."]);
+    board.fields[2][2].field_state = FieldState::Flagged;
+
+    board.chord_field(1, 1);
+
+    assert_eq!(board.fields[0][0].field_state, FieldState::Open);
+    assert!(board.fields[0][0].mine);
+}
+
+#[test]
+fn chords_are_a_v2_section() {
+    let parsed_data = parse_game_data("2=3x3,10+11+001++002").unwrap();
+    assert_eq!(parsed_data.chord_data.len(), 1);
+    assert_eq!(
+        (
+            parsed_data.chord_data[0].x,
+            parsed_data.chord_data[0].y,
+            parsed_data.chord_data[0].total_time
+        ),
+        (0, 0, 2)
+    );
+
+    assert!(matches!(
+        parse_game_data("1=3x3+11+001++002"),
+        Err(MinesweeperError::DataParse)
+    ));
+    assert!(parse_game_data("1=3x3+11+001+").is_ok());
+}
+
+#[test]
+fn still_image_matches_the_last_gif_frame() {
+    // The column of mines keeps both halves apart. The chord on the left happens before its flag
+    // and has no effect, the one on the right comes after its flag and opens the neighbours.
+    let rows = ["*..*..*", ".o.*.o.", "...*..."];
+    let moves = [
+        open(1, 1, 1),
+        open(5, 1, 1),
+        chord(1, 1, 2),
+        flag(0, 0, 3),
+        flag(6, 0, 4),
+        chord(5, 1, 5),
+    ];
+    let renderer = || Renderer::from_parsed(replay(&rows, &moves), true);
+
+    let still = image::load_from_memory(&renderer().render_jpeg().unwrap())
+        .unwrap()
+        .to_rgba8();
+
+    let gif: Vec<u8> = renderer()
+        .into_gif_stream()
+        .and_then(|gif_stream| gif_stream.collect::<Result<Vec<_>, _>>())
+        .unwrap()
+        .concat();
+    let frames = GifDecoder::new(Cursor::new(gif))
+        .unwrap()
+        .into_frames()
+        .collect_frames()
+        .unwrap();
+    let last = frames.last().unwrap().buffer();
+
+    assert_eq!(still.dimensions(), last.dimensions());
+    // The progress indicators differ, the boards above them have to be the same.
+    // Transparent pixels of the textures show the previous frames in the gif, so only opaque ones count.
+    let board_height = 3 * still.width() / 7;
+    for y in 0..board_height {
+        for x in 0..still.width() {
+            let pixel = still.get_pixel(x, y);
+            if pixel.0[3] == 255 {
+                assert_eq!(pixel, last.get_pixel(x, y), "{x},{y}");
+            }
+        }
+    }
+
+    // Only the right chord opened its neighbours
+    assert_eq!(
+        renderer().render_text(TextStyle::Ascii),
+        "F###21F\n#1##311\n####2..\n"
+    );
+}
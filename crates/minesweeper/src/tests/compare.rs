@@ -0,0 +1,92 @@
+use crate::compare::{Comparison, MAX_PLAYERS};
+use crate::error::MinesweeperError;
+use crate::renderer::{FrameSource, GifStream, ProgressStyle, ReplayFrames};
+use crate::tests::renderer;
+
+const WIDE_V2: &str = include_str!("fixtures/wide_v2.txt");
+const WIDE_V2_RIVAL: &str = include_str!("fixtures/wide_v2_rival.txt");
+const LOST_V2: &str = include_str!("fixtures/lost_v2.txt");
+const LARGE_V2: &str = include_str!("fixtures/large_v2.txt");
+
+fn frames(game_data: &str) -> ReplayFrames {
+    renderer(game_data, true)
+        .with_progress_style(ProgressStyle::Cells)
+        .into_frames()
+}
+
+#[test]
+fn comparison_renders_boards_side_by_side() {
+    let comparison = Comparison::new(vec![
+        ("A: WIDE".to_string(), frames(WIDE_V2)),
+        ("B: RIVAL".to_string(), frames(WIDE_V2_RIVAL)),
+    ])
+    .unwrap();
+
+    let gif: Vec<u8> = GifStream::new(comparison)
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap()
+        .concat();
+
+    let mut decoder = gif::DecodeOptions::new().read_info(gif.as_slice()).unwrap();
+    assert_eq!((decoder.width(), decoder.height()), (2 * 256 + 8, 32 + 100));
+
+    let mut frames = 0;
+    while decoder.read_next_frame().unwrap().is_some() {
+        frames += 1;
+    }
+    // Untouched boards and one frame for each of the ticks 1, 2, 3 and 4 of both games
+    assert_eq!(frames, 5);
+}
+
+#[test]
+fn race_lays_out_different_boards_in_a_grid() {
+    let race = Comparison::new(vec![
+        ("1: WIDE".to_string(), frames(WIDE_V2)),
+        ("2: RIVAL".to_string(), frames(WIDE_V2_RIVAL)),
+        ("3: LOST".to_string(), frames(LOST_V2)),
+    ])
+    .unwrap();
+    assert_eq!(race.dimensions(), (2 * 256 + 8, 2 * (32 + 100) + 8));
+
+    let gif: Vec<u8> = GifStream::new(race)
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap()
+        .concat();
+    let mut decoder = gif::DecodeOptions::new().read_info(gif.as_slice()).unwrap();
+    assert_eq!((decoder.width(), decoder.height()), (520, 272));
+    while decoder.read_next_frame().unwrap().is_some() {}
+}
+
+#[test]
+fn grid_keeps_boards_within_a_single_gif() {
+    assert_eq!(Comparison::max_board_dimension(2), 512);
+    assert_eq!(Comparison::max_board_dimension(8), 341);
+}
+
+/// The same large board for every player, shrunk like the routes do
+fn large_race(players: usize) -> Result<Comparison, MinesweeperError> {
+    let frames = renderer(LARGE_V2, true)
+        .with_max_dimension(Comparison::max_board_dimension(players))
+        .with_progress_style(ProgressStyle::Cells)
+        .into_frames();
+
+    Comparison::new(
+        (1..=players)
+            .map(|player| (player.to_string(), frames.clone()))
+            .collect(),
+    )
+}
+
+#[test]
+fn grid_of_the_largest_boards_is_limited() {
+    // Cells stop shrinking at the smallest tile size, so the grid gets larger than a gif
+    let race = large_race(MAX_PLAYERS).unwrap();
+    assert_eq!(race.dimensions().0, 3 * 200 * 4 + 2 * 8);
+
+    assert!(matches!(
+        large_race(6 * 6),
+        Err(MinesweeperError::UnsupportedBoardSize)
+    ));
+}
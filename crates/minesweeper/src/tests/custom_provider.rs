@@ -0,0 +1,248 @@
+use crate::error::MinesweeperError;
+use crate::profile::fetch_profile;
+use crate::provider::config::{ConfigValues, ProviderConfig};
+use crate::provider::custom::custom_provider::{
+    CustomConfig, CustomProvider, CUSTOM_PROVIDERS, CUSTOM_PROVIDER_TOKENS,
+};
+use crate::provider::custom::json_path::JsonPath;
+use crate::provider::http::{HttpResponse, MockClient};
+use crate::provider::provider::{EnumProviders, GamePlayer, Provider};
+
+const REPLAY_V1_9X9: &str = include_str!("fixtures/replay_v1_9x9.txt");
+const URL: &str = "https://api.example.com/game/0c9bf2a2-1c1a-4a53-a6a4-3a3c1e2c1f5d";
+const GAME_ID: &str = "0C9BF2A21C1A4A53A6A43A3C1E2C1F5D";
+const UUID: &str = "0c9bf2a2-1c1a-4a53-a6a4-3a3c1e2c1f5d";
+const GAMES_URL: &str = "https://api.other.net/player/0c9bf2a2-1c1a-4a53-a6a4-3a3c1e2c1f5d";
+
+/// Two servers, `example` reads only the game data while `other` maps everything
+const PROVIDERS: &str = r#"{
+    "example": {
+        "url": "https://api.example.com/game/{gameid}",
+        "gameIds": ["uuid"],
+        "gameData": "$.games[0]['game data']"
+    },
+    "other": {
+        "name": "Other Network",
+        "url": "https://api.other.net/game/{gameid}",
+        "authHeader": "X-Token",
+        "gameData": "$.notation",
+        "fields": {"won": "$.result.won", "time": "$.result.ms"},
+        "players": {"list": "$.players", "uuid": "$.id", "name": "$.nick", "group": "$.rank"},
+        "recentGames": {
+            "url": "https://api.other.net/player/{uuid}",
+            "list": "$.games",
+            "id": "$.id",
+            "won": "$.won",
+            "time": "$.ms",
+            "board": "$.size",
+            "players": {"list": "$.players", "uuid": "$.id", "name": "$.nick"}
+        }
+    }
+}"#;
+
+fn values(providers: &str, tokens: Option<&str>) -> ConfigValues {
+    let mut values = ConfigValues::default();
+    values.insert(CUSTOM_PROVIDERS, providers.to_string());
+    if let Some(tokens) = tokens {
+        values.insert(CUSTOM_PROVIDER_TOKENS, tokens.to_string());
+    }
+    values
+}
+
+fn provider(config: &CustomConfig, id: &str) -> CustomProvider {
+    config
+        .providers()
+        .find(|provider| provider.id() == id)
+        .unwrap()
+}
+
+fn answer(fields: &str) -> String {
+    format!(
+        r#"{{"games": [{{"game data": "{}"{fields}}}]}}"#,
+        REPLAY_V1_9X9.trim()
+    )
+}
+
+#[test]
+fn json_paths_select_values() {
+    let json = serde_json::json!({"data": {"games": [{"id": 1}, {"id": 2}]}});
+
+    let path: JsonPath = "$.data.games[1].id".parse().unwrap();
+    assert_eq!(path.select(&json), Some(&2.into()));
+    let missing: JsonPath = "$.data.games[5].id".parse().unwrap();
+    assert_eq!(missing.select(&json), None);
+    let root: JsonPath = "$".parse().unwrap();
+    assert_eq!(root.select(&json), Some(&json));
+}
+
+#[test]
+fn broken_json_paths_are_rejected() {
+    for path in ["data.games", "$.", "$[1", "$[x]", "$.a b"] {
+        assert!(
+            path.parse::<JsonPath>().is_err(),
+            "{path} should be invalid"
+        );
+    }
+}
+
+#[test]
+fn every_configured_server_is_a_provider() {
+    let config = CustomConfig::from_values(&values(PROVIDERS, None)).unwrap();
+
+    let providers: Vec<_> = config
+        .providers()
+        .map(|provider| (provider.id().to_string(), provider.name().to_string()))
+        .collect();
+    assert_eq!(
+        providers,
+        [
+            ("example".to_string(), "example".to_string()),
+            ("other".to_string(), "Other Network".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn invalid_settings_are_rejected() {
+    let invalid = [
+        r#"{"example": {"url": "https://api.example.com/game", "gameData": "$.data"}}"#,
+        r#"{"example": {"url": "https://a.com/{gameid}", "gameData": "$.data", "fields": {"colour": "$.colour"}}}"#,
+        r#"{"example": {"url": "https://a.com/{gameid}", "gameData": "$.data", "gameIds": ["uuid", "hex"]}}"#,
+        r#"{"example": {"url": "https://a.com/{gameid}", "gameData": "data"}}"#,
+        r#"{"example": {"url": "https://a.com/{gameid}", "gameData": "$.data", "colour": "red"}}"#,
+        r#"{"example": {"url": "https://a.com/{gameid}", "gameData": "$.data", "recentGames": {"url": "https://a.com/player", "list": "$", "id": "$.id", "won": "$.won", "time": "$.ms"}}}"#,
+        "example",
+    ];
+    for providers in invalid {
+        assert!(
+            matches!(
+                CustomConfig::from_values(&values(providers, None)),
+                Err(MinesweeperError::InvalidConfig(CUSTOM_PROVIDERS))
+            ),
+            "{providers} should be invalid"
+        );
+    }
+
+    assert!(matches!(
+        CustomConfig::from_values(&values(PROVIDERS, Some(r#"{"unknown": "secret"}"#))),
+        Err(MinesweeperError::InvalidConfig(CUSTOM_PROVIDER_TOKENS))
+    ));
+    assert!(matches!(
+        CustomConfig::from_values(&ConfigValues::default()),
+        Err(MinesweeperError::MissingConfig(CUSTOM_PROVIDERS))
+    ));
+}
+
+#[test]
+fn configured_fields_and_players_are_read() {
+    let client = MockClient::default().with_response(
+        "https://api.other.net/game/abc",
+        HttpResponse::new(
+            200,
+            format!(
+                r#"{{"notation": "{}", "result": {{"won": false, "ms": 20000}},
+                    "players": [{{"id": "{UUID}", "nick": "Alex_1607", "rank": "vip"}}]}}"#,
+                REPLAY_V1_9X9.trim()
+            ),
+        ),
+    );
+
+    let config =
+        CustomConfig::from_values(&values(PROVIDERS, Some(r#"{"other": "secret"}"#))).unwrap();
+    let api_data =
+        pollster::block_on(provider(&config, "other").fetch_data(&client, "abc", &config)).unwrap();
+
+    assert_eq!(api_data.game_data.as_deref(), Some(REPLAY_V1_9X9.trim()));
+    assert_eq!(api_data.won, Some(false));
+    assert_eq!(api_data.time, Some(20000));
+    assert_eq!(
+        api_data.players,
+        Some(vec![GamePlayer {
+            uuid: UUID.to_string(),
+            name: "Alex_1607".to_string(),
+            group: Some("vip".to_string()),
+        }])
+    );
+    assert_eq!(api_data.player_name(), Some("Alex_1607"));
+    assert_eq!(client.requests()[0].header("X-Token"), Some("secret"));
+}
+
+#[test]
+fn unreported_results_stay_unset() {
+    let client = MockClient::default().with_response(URL, HttpResponse::new(200, answer("")));
+
+    let config = CustomConfig::from_values(&values(PROVIDERS, None)).unwrap();
+    let api_data =
+        pollster::block_on(provider(&config, "example").fetch_data(&client, GAME_ID, &config))
+            .unwrap();
+
+    assert_eq!(api_data.won, None);
+    assert_eq!(api_data.time, None);
+    assert_eq!(api_data.players, None);
+    assert!(client.requests()[0].header("Authorization").is_none());
+}
+
+#[test]
+fn missing_game_data_is_reported() {
+    let client =
+        MockClient::default().with_response(URL, HttpResponse::new(200, r#"{"games": []}"#));
+
+    let config = CustomConfig::from_values(&values(PROVIDERS, None)).unwrap();
+    let result =
+        pollster::block_on(provider(&config, "example").fetch_data(&client, GAME_ID, &config));
+    assert!(matches!(result, Err(MinesweeperError::GameDataNotFound)));
+}
+
+#[test]
+fn custom_profiles_come_from_the_recent_games() {
+    let client = MockClient::default().with_response(
+        GAMES_URL,
+        HttpResponse::new(
+            200,
+            format!(
+                r#"{{"games": [
+                    {{"id": 7, "won": true, "ms": 9000, "size": "9x9",
+                      "players": [{{"id": "{UUID}", "nick": "Alex_1607"}}]}},
+                    {{"id": "8", "won": false, "ms": 1000, "size": "9x9"}}
+                ]}}"#
+            ),
+        ),
+    );
+
+    let config = CustomConfig::from_values(&values(PROVIDERS, None)).unwrap();
+    let provider = EnumProviders::Custom(provider(&config, "other"));
+    let profile = pollster::block_on(fetch_profile(
+        &provider,
+        &client,
+        UUID,
+        &values(PROVIDERS, None),
+    ))
+    .unwrap();
+
+    assert_eq!(profile.name.as_deref(), Some("Alex_1607"));
+    let ids: Vec<_> = profile
+        .recent_games
+        .iter()
+        .flatten()
+        .map(|game| game.id.as_str())
+        .collect();
+    assert_eq!(ids, ["7", "8"]);
+    assert_eq!(profile.win_rate, Some(0.5));
+}
+
+#[test]
+fn custom_servers_without_recent_games_have_no_profiles() {
+    let client = MockClient::default();
+
+    let config = CustomConfig::from_values(&values(PROVIDERS, None)).unwrap();
+    let provider = EnumProviders::Custom(provider(&config, "example"));
+    let result = pollster::block_on(fetch_profile(
+        &provider,
+        &client,
+        UUID,
+        &values(PROVIDERS, None),
+    ));
+
+    assert!(matches!(result, Err(MinesweeperError::Unsupported)));
+    assert!(client.requests().is_empty());
+}
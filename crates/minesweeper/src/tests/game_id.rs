@@ -0,0 +1,102 @@
+use crate::base36;
+use crate::error::MinesweeperError;
+use crate::provider::game_id::{normalize_game_id, GameIdFormat};
+use crate::provider::greev::greev_provider::{GreevConfig, GreevProvider};
+use crate::provider::http::MockClient;
+use crate::provider::mcplayhd::mcplay_provider::{McPlayHdConfig, McPlayHdProvider};
+use crate::provider::provider::Provider;
+
+const UUID: &str = "0c9bf2a2-1c1a-4a53-a6a4-3a3c1e2c1f5d";
+
+#[test]
+fn base36_decodes_case_insensitive() {
+    assert_eq!(base36::decode("zz"), Some(1295));
+    assert_eq!(base36::decode("ZZ"), Some(1295));
+    assert_eq!(base36::decode("0"), Some(0));
+}
+
+#[test]
+fn base36_rejects_invalid_numbers() {
+    for number in ["", "a-b", "ä", "1 2", "zzzzzzzzzzzzzzzz"] {
+        assert_eq!(base36::decode(number), None, "{number} should be invalid");
+    }
+}
+
+#[test]
+fn formats_normalize_their_ids() {
+    assert_eq!(
+        GameIdFormat::Base36.normalize("zz"),
+        Some("1295".to_string())
+    );
+    assert_eq!(
+        GameIdFormat::Numeric.normalize("0042"),
+        Some("42".to_string())
+    );
+    assert_eq!(GameIdFormat::Numeric.normalize("+42"), None);
+    assert_eq!(
+        GameIdFormat::PrefixedNumeric.normalize("id:0042"),
+        Some("42".to_string())
+    );
+    assert_eq!(GameIdFormat::PrefixedNumeric.normalize("42"), None);
+    assert_eq!(GameIdFormat::PrefixedNumeric.normalize("id:zz"), None);
+    assert_eq!(GameIdFormat::Uuid.normalize(UUID), Some(UUID.to_string()));
+    assert_eq!(
+        GameIdFormat::Uuid.normalize("0C9BF2A21C1A4A53A6A43A3C1E2C1F5D"),
+        Some(UUID.to_string())
+    );
+    assert_eq!(
+        GameIdFormat::Uuid.normalize("0c9bf2a2-1c1a-4a53-a6a4-3a3c1e2c1f5"),
+        None
+    );
+    assert_eq!(
+        GameIdFormat::Token.normalize("Ab-1_z"),
+        Some("Ab-1_z".to_string())
+    );
+    assert_eq!(GameIdFormat::Token.normalize("../admin"), None);
+}
+
+#[test]
+fn the_first_matching_format_wins() {
+    let formats = [GameIdFormat::Uuid, GameIdFormat::Numeric];
+
+    assert_eq!(
+        normalize_game_id(&formats, &UUID.to_uppercase()).unwrap(),
+        UUID
+    );
+    assert_eq!(normalize_game_id(&formats, " 12 ").unwrap(), "12");
+    assert!(matches!(
+        normalize_game_id(&formats, "abc"),
+        Err(MinesweeperError::InvalidGameId(id)) if id == "abc"
+    ));
+}
+
+#[test]
+fn mcplayhd_takes_short_and_prefixed_numeric_ids() {
+    let config = McPlayHdConfig {
+        api_key: "secret".to_string(),
+    };
+    let normalize = |game_id| {
+        McPlayHdProvider
+            .normalize_game_id(game_id, &config)
+            .unwrap()
+    };
+
+    assert_eq!(normalize("zz"), "1295");
+    assert_eq!(normalize("id:1295"), "1295");
+    // Without the prefix digits are a short id as well
+    assert_eq!(normalize("1295"), "49577");
+}
+
+#[test]
+fn invalid_ids_are_never_sent_upstream() {
+    let client = MockClient::default();
+    let config = McPlayHdConfig {
+        api_key: "secret".to_string(),
+    };
+
+    let mcplayhd = pollster::block_on(McPlayHdProvider.fetch_data(&client, "a/b", &config));
+    assert!(matches!(mcplayhd, Err(MinesweeperError::InvalidGameId(_))));
+    let greev = pollster::block_on(GreevProvider.fetch_data(&client, "abc?x=1", &GreevConfig));
+    assert!(matches!(greev, Err(MinesweeperError::InvalidGameId(_))));
+    assert!(client.requests().is_empty());
+}
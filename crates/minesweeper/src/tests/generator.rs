@@ -0,0 +1,70 @@
+use crate::analysis::anticheat::{analyse, FirstClick};
+use crate::parsers::parser::Generator;
+use crate::tests::{open, replay};
+
+#[test]
+fn generator_names_are_recognised() {
+    let cases = [
+        ("random", Generator::Random),
+        ("classic", Generator::Random),
+        ("default", Generator::Random),
+        ("safe", Generator::FirstClickSafe),
+        ("firstClickSafe", Generator::FirstClickSafe),
+        ("safe_first_click", Generator::FirstClickSafe),
+        ("Safe Start", Generator::FirstClickSafe),
+        ("opening", Generator::FirstClickOpening),
+        ("FIRST_CLICK_OPENING", Generator::FirstClickOpening),
+        ("opening-first-click", Generator::FirstClickOpening),
+        ("zerostart", Generator::FirstClickOpening),
+        ("zero", Generator::FirstClickOpening),
+        ("noGuess", Generator::NoGuess),
+        ("no-guessing", Generator::NoGuess),
+        ("solvable", Generator::NoGuess),
+        ("NOG", Generator::NoGuess),
+    ];
+
+    for (name, generator) in cases {
+        assert_eq!(Generator::from(name), generator, "{name}");
+    }
+}
+
+#[test]
+fn unknown_generators_keep_their_name() {
+    assert_eq!(
+        Generator::from("Mirror Mode"),
+        Generator::Unknown("Mirror Mode".to_string())
+    );
+    assert_eq!(Generator::from(""), Generator::Unknown(String::new()));
+}
+
+#[test]
+fn only_protecting_generators_guarantee_a_safe_first_click() {
+    assert!(!Generator::Random.safe_first_click());
+    assert!(Generator::FirstClickSafe.safe_first_click());
+    assert!(Generator::FirstClickOpening.safe_first_click());
+    assert!(Generator::NoGuess.safe_first_click());
+    assert!(!Generator::Unknown("custom".to_string()).safe_first_click());
+}
+
+fn first_click(x: usize, generator: Option<&str>) -> FirstClick {
+    // A mine, a number and a zero to click on first
+    let parsed_data =
+        replay(&["*...."], &[open(x, 0, 1)]).with_generator(generator.map(Generator::from));
+    analyse(&parsed_data).first_click
+}
+
+#[test]
+fn first_click_is_judged_by_the_generator() {
+    // Without a known guarantee hitting a mine is bad luck
+    assert_eq!(first_click(0, None), FirstClick::Mine);
+    assert_eq!(first_click(0, Some("random")), FirstClick::Mine);
+    assert_eq!(first_click(0, Some("mirror")), FirstClick::Mine);
+
+    assert_eq!(first_click(0, Some("safe")), FirstClick::GeneratorBug);
+    assert_eq!(first_click(0, Some("noguess")), FirstClick::GeneratorBug);
+    assert_eq!(first_click(1, Some("safe")), FirstClick::Safe);
+
+    // An opening generator also promises a zero
+    assert_eq!(first_click(1, Some("opening")), FirstClick::GeneratorBug);
+    assert_eq!(first_click(3, Some("opening")), FirstClick::Safe);
+}
@@ -0,0 +1,138 @@
+mod anticheat;
+mod chords;
+mod compare;
+mod custom_provider;
+mod game_id;
+mod generator;
+mod non_square;
+mod probability;
+mod profile;
+mod progress;
+mod providers;
+mod snapshots;
+mod solver;
+mod stats;
+mod svg;
+mod text;
+mod video;
+
+use crate::analysis::replay::{Move, MoveKind};
+use crate::minesweeper_logic::{Board, Field, FieldState};
+use crate::parsers::parser::{
+    parse_game_data, Action, ChordAction, FlagAction, Metadata, OpenAction, ParsedData,
+};
+use crate::renderer::Renderer;
+
+/// Parses the game data of a fixture file.
+pub(crate) fn fixture(game_data: &str) -> ParsedData {
+    parse_game_data(game_data.trim()).expect("fixture should parse")
+}
+
+/// Renderer for the game data of a fixture file.
+pub(crate) fn renderer(game_data: &str, gif: bool) -> Renderer {
+    Renderer::from_parsed(fixture(game_data), gif)
+}
+
+/// Builds a board from rows of cells: `*` is a closed mine, `.` a closed safe cell,
+/// `o` an open safe cell and `x` an open mine. Numbers are counted from the mines.
+pub(crate) fn board(rows: &[&str]) -> Board {
+    let cells: Vec<Vec<char>> = rows.iter().map(|row| row.chars().collect()).collect();
+    let (width, height) = (cells[0].len(), cells.len());
+    let mine = |x: i32, y: i32| {
+        (0..width as i32).contains(&x)
+            && (0..height as i32).contains(&y)
+            && matches!(cells[y as usize][x as usize], '*' | 'x')
+    };
+
+    let fields: Vec<Vec<Field>> = cells
+        .iter()
+        .enumerate()
+        .map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .map(|(x, cell)| {
+                    let (x, y) = (x as i32, y as i32);
+                    let value = (-1..=1)
+                        .flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
+                        .filter(|(dx, dy)| (*dx, *dy) != (0, 0) && mine(x + dx, y + dy))
+                        .count() as u8;
+                    Field {
+                        value,
+                        field_state: match cell {
+                            'o' | 'x' => FieldState::Open,
+                            _ => FieldState::Closed,
+                        },
+                        mine: mine(x, y),
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    let count = |matches: fn(&char) -> bool| cells.iter().flatten().filter(|c| matches(c)).count();
+    Board {
+        changed_fields: vec![vec![true; width]; height],
+        metadata: Metadata {
+            x_size: width as i32,
+            y_size: height as i32,
+            timeunits: 1,
+            generator: None,
+        },
+        open_fields: count(|cell| matches!(cell, 'o' | 'x')) as u32,
+        mine_count: count(|cell| matches!(cell, '*' | 'x')) as u32,
+        total_fields: (width * height) as u32,
+        fields,
+    }
+}
+
+/// Wraps a board from [board] and the moves played on it into a replay.
+pub(crate) fn replay(rows: &[&str], moves: &[Move]) -> ParsedData {
+    let game_board = board(rows);
+    let mut parsed_data = ParsedData {
+        metadata: game_board.metadata.clone(),
+        game_board,
+        open_data: Vec::new(),
+        flag_data: Vec::new(),
+        chord_data: Vec::new(),
+    };
+
+    for mv in moves {
+        let (x, y, total_time) = (mv.x as i32, mv.y as i32, mv.total_time);
+        match &mv.kind {
+            MoveKind::Open => parsed_data.open_data.push(OpenAction {
+                x,
+                y,
+                time: 0,
+                total_time,
+            }),
+            MoveKind::Flag(action) => parsed_data.flag_data.push(FlagAction {
+                x,
+                y,
+                time: 0,
+                action: action.clone(),
+                total_time,
+            }),
+            MoveKind::Chord => parsed_data
+                .chord_data
+                .push(ChordAction { x, y, total_time }),
+        }
+    }
+
+    parsed_data
+}
+
+pub(crate) fn open(x: usize, y: usize, total_time: i64) -> Move {
+    Move {
+        x,
+        y,
+        total_time,
+        kind: MoveKind::Open,
+    }
+}
+
+pub(crate) fn flag(x: usize, y: usize, total_time: i64) -> Move {
+    Move {
+        kind: MoveKind::Flag(Action::Place),
+        ..open(x, y, total_time)
+    }
+}
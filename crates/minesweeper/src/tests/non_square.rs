@@ -0,0 +1,165 @@
+use crate::error::MinesweeperError;
+use crate::minesweeper_logic::FieldState;
+use crate::parsers::parser::{parse_game_data, ParsedData};
+use crate::renderer::{FrameSource, Renderer};
+use crate::tests::fixture;
+
+const WIDE_V2: &str = include_str!("fixtures/wide_v2.txt");
+const TALL_V1: &str = include_str!("fixtures/tall_v1.txt");
+const LARGE_V2: &str = include_str!("fixtures/large_v2.txt");
+
+fn play(parsed_data: &mut ParsedData) {
+    let board = &mut parsed_data.game_board;
+    parsed_data
+        .flag_data
+        .iter()
+        .for_each(|flag| flag.perform_action(board));
+    parsed_data
+        .open_data
+        .iter()
+        .for_each(|open| board.open_field(open.x as usize, open.y as usize));
+}
+
+fn render(parsed_data: ParsedData, gif: bool) -> (u32, u32) {
+    let mut renderer = Renderer::from_parsed(parsed_data, gif);
+    let data = if gif {
+        renderer
+            .into_gif_stream()
+            .and_then(|gif_stream| gif_stream.collect::<Result<Vec<_>, _>>())
+            .map(|chunks| chunks.concat())
+    } else {
+        renderer.render_jpeg()
+    }
+    .expect("fixture should render");
+
+    let image = image::load_from_memory(&data).expect("rendered image should decode");
+    (image.width(), image.height())
+}
+
+#[test]
+fn wide_board_is_indexed_by_row() {
+    let parsed_data = fixture(WIDE_V2);
+    let board = &parsed_data.game_board;
+
+    assert_eq!(board.fields.len(), 3);
+    assert!(board.fields.iter().all(|row| row.len() == 8));
+    assert!(board.fields[0][7].mine);
+    assert!(board.fields[2][6].mine);
+    assert_eq!(board.mine_count, 2);
+
+    let values: Vec<Vec<u8>> = board
+        .fields
+        .iter()
+        .map(|row| row.iter().map(|field| field.value).collect())
+        .collect();
+    assert_eq!(
+        values,
+        vec![
+            vec![0, 0, 0, 0, 0, 0, 1, 0],
+            vec![0, 0, 0, 0, 0, 1, 2, 2],
+            vec![0, 0, 0, 0, 0, 1, 0, 1],
+        ]
+    );
+}
+
+#[test]
+fn wide_board_cascades_and_wins() {
+    let mut parsed_data = fixture(WIDE_V2);
+    play(&mut parsed_data);
+    let board = &parsed_data.game_board;
+
+    assert_eq!(board.open_fields, 22);
+    assert_eq!(board.calculate_done_percentage(), 100);
+    assert_eq!(board.fields[0][7].field_state, FieldState::Flagged);
+    assert_eq!(board.fields[2][6].field_state, FieldState::Closed);
+}
+
+#[test]
+fn tall_v1_board_keeps_row_first_mines() {
+    let mut parsed_data = fixture(TALL_V1);
+    assert_eq!(parsed_data.game_board.fields.len(), 8);
+    assert!(parsed_data.game_board.fields[7][0].mine);
+    assert!(parsed_data.game_board.fields[0][2].mine);
+
+    play(&mut parsed_data);
+    assert_eq!(parsed_data.game_board.open_fields, 22);
+}
+
+#[test]
+fn non_square_boards_render_with_their_dimensions() {
+    assert_eq!(render(fixture(WIDE_V2), false), (8 * 32, 3 * 32 + 4));
+    assert_eq!(render(fixture(WIDE_V2), true), (8 * 32, 3 * 32 + 4));
+    assert_eq!(render(fixture(TALL_V1), false), (3 * 32, 8 * 32 + 4));
+    assert_eq!(render(fixture(TALL_V1), true), (3 * 32, 8 * 32 + 4));
+}
+
+#[test]
+fn large_board_cascades_without_recursion() {
+    let mut parsed_data = fixture(LARGE_V2);
+    assert!(parsed_data.game_board.fields[149][199].mine);
+
+    play(&mut parsed_data);
+    assert_eq!(parsed_data.game_board.open_fields, 200 * 150 - 1);
+}
+
+#[test]
+fn large_board_renders_with_scaled_tiles() {
+    // The gif gets 5 pixel cells to stay within 1024 pixels, the still image 20 pixel cells
+    // to stay within 4096
+    let dimensions = |gif| Renderer::from_parsed(fixture(LARGE_V2), gif).dimensions();
+    assert_eq!(dimensions(true), (200 * 5, 150 * 5 + 4));
+    assert_eq!(dimensions(false), (200 * 20, 150 * 20 + 4));
+
+    // Only the first gif frame gets drawn, the whole replay would take too long for every test run
+    let frame = Renderer::from_parsed(fixture(LARGE_V2), true)
+        .into_frames()
+        .next_frame()
+        .expect("replay should have a first frame")
+        .expect("first frame should render");
+    assert_eq!(frame.image.dimensions(), (200 * 5, 150 * 5 + 4));
+    assert!(frame.image.pixels().all(|pixel| pixel.0[3] == 255));
+}
+
+#[test]
+fn oversized_board_is_rejected() {
+    assert!(matches!(
+        parse_game_data("2=201x10,10+++"),
+        Err(MinesweeperError::UnsupportedBoardSize)
+    ));
+    assert!(matches!(
+        parse_game_data("1=0x10+++"),
+        Err(MinesweeperError::UnsupportedBoardSize)
+    ));
+}
+
+#[test]
+fn actions_outside_the_board_are_rejected() {
+    assert!(matches!(
+        parse_game_data("2=8x3,10++0|3:1+"),
+        Err(MinesweeperError::DataParse)
+    ));
+}
+
+#[test]
+fn mines_outside_the_board_are_rejected() {
+    assert!(matches!(
+        parse_game_data("2=2x1,10+00;00;00;10;zz+00+"),
+        Err(MinesweeperError::DataParse)
+    ));
+    assert!(matches!(
+        parse_game_data("1=2x1,10+0|5++"),
+        Err(MinesweeperError::DataParse)
+    ));
+}
+
+#[test]
+fn duplicate_mines_are_rejected() {
+    assert!(matches!(
+        parse_game_data("2=2x1,10+00;00+00+"),
+        Err(MinesweeperError::DataParse)
+    ));
+    assert!(matches!(
+        parse_game_data("1=2x1,10+0|0;0|0++"),
+        Err(MinesweeperError::DataParse)
+    ));
+}
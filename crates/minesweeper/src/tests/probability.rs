@@ -0,0 +1,40 @@
+use crate::analysis::probability::probability_map;
+use crate::tests::board;
+
+fn assert_close(actual: Option<f32>, expected: f32) {
+    let actual = actual.expect("cell is closed");
+    assert!(
+        (actual - expected).abs() < 1e-6,
+        "expected {expected}, got {actual}"
+    );
+}
+
+#[test]
+fn fifty_fifty_pair_shares_the_mine() {
+    let map = probability_map(&board(&["*.", "oo"]), &[], None);
+
+    assert_close(map.cells[0][0], 0.5);
+    assert_close(map.cells[0][1], 0.5);
+    assert_eq!(map.cells[1], [None, None]);
+}
+
+#[test]
+fn single_candidate_is_certainly_a_mine() {
+    let map = probability_map(&board(&["o*."]), &[], None);
+
+    assert_close(map.cells[0][1], 1.0);
+    assert_close(map.cells[0][2], 0.0);
+}
+
+#[test]
+fn interior_cells_share_the_remaining_mines() {
+    // The open zeros prove their five neighbours safe, leaving 2 mines for the 7 cells beyond
+    let map = probability_map(&board(&["oo..", "oo..", "...*", "..*."]), &[], None);
+
+    for (x, y) in [(2, 0), (2, 1), (2, 2), (0, 2), (1, 2)] {
+        assert_close(map.cells[y][x], 0.0);
+    }
+    for (x, y) in [(3, 0), (3, 1), (3, 2), (0, 3), (1, 3), (2, 3), (3, 3)] {
+        assert_close(map.cells[y][x], 2.0 / 7.0);
+    }
+}
@@ -0,0 +1,281 @@
+use std::io::Cursor;
+
+use crate::error::MinesweeperError;
+use crate::profile::{fetch_profile, BestTime, PlayerProfile, RecentGame};
+use crate::provider::config::ConfigValues;
+use crate::provider::game_id::GameIdFormat;
+use crate::provider::greev::greev_provider::{GreevConfig, GreevProvider};
+use crate::provider::http::{HttpClient, HttpResponse, MockClient};
+use crate::provider::mcplayhd::mcplay_provider::{McPlayHdProvider, MCPLAYHD_API_KEY};
+use crate::provider::provider::{ApiData, EnumProviders, Provider};
+
+const UUID: &str = "0c9bf2a2-1c1a-4a53-a6a4-3a3c1e2c1f5d";
+const GREEV_NAME_URL: &str =
+    "https://api.greev.eu/v2/player/name/0c9bf2a2-1c1a-4a53-a6a4-3a3c1e2c1f5d";
+const MCPLAYHD_GAMES_URL: &str =
+    "https://mcplayhd.net/api/v1/minesweeper/player/0c9bf2a2-1c1a-4a53-a6a4-3a3c1e2c1f5d/games";
+
+const MCPLAYHD_GAMES: &str = r#"{
+    "status": 200,
+    "data": [
+        {
+            "gameInfo": {
+                "id": 1295,
+                "uuid": "5d2b7c8e-2f0a-4c4e-9d7b-1a2b3c4d5e6f",
+                "won": true,
+                "flagsCorrect": 10,
+                "flagsIncorrect": 0,
+                "timeStart": 1700000000000,
+                "timeEnd": 1700000012345,
+                "timeTaken": 12345,
+                "mines": 10,
+                "sizeX": 9,
+                "sizeZ": 9,
+                "algebraicNotation": "2=4x2,10+30+101;302+001P"
+            },
+            "players": [
+                {
+                    "uuid": "0C9BF2A21C1A4A53A6A43A3C1E2C1F5D",
+                    "name": "Alex_1607",
+                    "group": "default"
+                }
+            ]
+        },
+        {
+            "gameInfo": {
+                "id": 1296,
+                "uuid": "6e3c8d9f-3a1b-4d5f-8e6c-2b3c4d5e6f70",
+                "won": false,
+                "flagsCorrect": 2,
+                "flagsIncorrect": 1,
+                "timeStart": 1700000100000,
+                "timeEnd": 1700000103000,
+                "timeTaken": 3000,
+                "mines": 10,
+                "sizeX": 9,
+                "sizeZ": 9,
+                "algebraicNotation": "2=4x2,10+30+101;302+001P"
+            },
+            "players": [
+                {
+                    "uuid": "0c9bf2a2-1c1a-4a53-a6a4-3a3c1e2c1f5d",
+                    "name": "Alex_1607",
+                    "group": "default"
+                }
+            ]
+        }
+    ]
+}"#;
+
+/// Provider which lists games but has no names, to cover the statistics
+struct ListingProvider;
+
+impl Provider for ListingProvider {
+    type Config = ConfigValues;
+
+    fn id(&self) -> &str {
+        "listing"
+    }
+
+    fn name(&self) -> &str {
+        "Listing"
+    }
+
+    fn game_id_formats(&self, _config: &ConfigValues) -> Vec<GameIdFormat> {
+        vec![GameIdFormat::Token]
+    }
+
+    async fn fetch_data(
+        &self,
+        _client: &impl HttpClient,
+        _game_id: &str,
+        _config: &ConfigValues,
+    ) -> Result<ApiData, MinesweeperError> {
+        Err(MinesweeperError::Unsupported)
+    }
+
+    async fn fetch_recent_games(
+        &self,
+        _client: &impl HttpClient,
+        _uuid: &str,
+        _config: &ConfigValues,
+    ) -> Result<Vec<RecentGame>, MinesweeperError> {
+        Ok(vec![
+            game("a", true, 12_000, Some("9x9")),
+            game("b", true, 9_500, Some("9x9")),
+            game("c", false, 3_000, Some("9x9")),
+            game("d", true, 48_000, Some("16x16")),
+            game("e", true, 1_000, None),
+        ])
+    }
+}
+
+/// Provider which knows nothing about players
+struct GamesOnlyProvider;
+
+impl Provider for GamesOnlyProvider {
+    type Config = ConfigValues;
+
+    fn id(&self) -> &str {
+        "games"
+    }
+
+    fn name(&self) -> &str {
+        "Games"
+    }
+
+    fn game_id_formats(&self, _config: &ConfigValues) -> Vec<GameIdFormat> {
+        vec![GameIdFormat::Token]
+    }
+
+    async fn fetch_data(
+        &self,
+        _client: &impl HttpClient,
+        _game_id: &str,
+        _config: &ConfigValues,
+    ) -> Result<ApiData, MinesweeperError> {
+        Err(MinesweeperError::Unsupported)
+    }
+}
+
+fn game(id: &str, won: bool, time: u64, board: Option<&str>) -> RecentGame {
+    RecentGame {
+        id: id.to_string(),
+        won,
+        time,
+        board: board.map(str::to_string),
+        players: None,
+    }
+}
+
+fn mcplayhd_config() -> ConfigValues {
+    let mut config = ConfigValues::default();
+    config.insert(MCPLAYHD_API_KEY, "secret".to_string());
+    config
+}
+
+fn card_size(profile: &PlayerProfile) -> (u32, u32) {
+    let decoder = png::Decoder::new(Cursor::new(profile.render_card().unwrap()));
+    let reader = decoder.read_info().unwrap();
+    (reader.info().width, reader.info().height)
+}
+
+#[test]
+fn greev_profile_has_the_name() {
+    let client = MockClient::default().with_response(
+        GREEV_NAME_URL,
+        HttpResponse::new(200, r#"{"name": "Alex1607"}"#),
+    );
+
+    let profile =
+        pollster::block_on(fetch_profile(&GreevProvider, &client, UUID, &GreevConfig)).unwrap();
+    assert_eq!(
+        profile,
+        PlayerProfile {
+            uuid: UUID.to_string(),
+            name: Some("Alex1607".to_string()),
+            recent_games: None,
+            win_rate: None,
+            best_times: None,
+        }
+    );
+}
+
+#[test]
+fn mcplayhd_profile_comes_from_the_players_of_the_games() {
+    let client = MockClient::default()
+        .with_response(MCPLAYHD_GAMES_URL, HttpResponse::new(200, MCPLAYHD_GAMES));
+    let provider = EnumProviders::McPlayHd(McPlayHdProvider);
+
+    let profile =
+        pollster::block_on(fetch_profile(&provider, &client, UUID, &mcplayhd_config())).unwrap();
+    assert_eq!(profile.name.as_deref(), Some("Alex_1607"));
+    let ids: Vec<_> = profile
+        .recent_games
+        .iter()
+        .flatten()
+        .map(|game| game.id.as_str())
+        .collect();
+    assert_eq!(ids, ["id:1295", "id:1296"]);
+    assert_eq!(profile.win_rate, Some(0.5));
+    assert_eq!(
+        profile.best_times,
+        Some(vec![BestTime {
+            board: "9x9".to_string(),
+            time: 12_345,
+        }])
+    );
+
+    let requests = client.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].header("Authorization"), Some("Bearer secret"));
+}
+
+#[test]
+fn providers_without_player_data_are_unsupported() {
+    let client = MockClient::default();
+
+    let result = pollster::block_on(fetch_profile(
+        &GamesOnlyProvider,
+        &client,
+        UUID,
+        &ConfigValues::default(),
+    ));
+    assert!(matches!(result, Err(MinesweeperError::Unsupported)));
+    assert!(client.requests().is_empty());
+}
+
+#[test]
+fn failed_name_lookups_are_errors() {
+    let client = MockClient::default().with_response(GREEV_NAME_URL, HttpResponse::new(404, ""));
+
+    let result = pollster::block_on(fetch_profile(&GreevProvider, &client, UUID, &GreevConfig));
+    assert!(matches!(result, Err(MinesweeperError::UpstreamNotFound)));
+}
+
+#[test]
+fn recent_games_give_win_rate_and_best_times() {
+    let profile = pollster::block_on(fetch_profile(
+        &ListingProvider,
+        &MockClient::default(),
+        UUID,
+        &ConfigValues::default(),
+    ))
+    .unwrap();
+
+    assert_eq!(profile.name, None);
+    assert_eq!(profile.recent_games.as_ref().map(Vec::len), Some(5));
+    assert_eq!(profile.win_rate, Some(0.8));
+    assert_eq!(
+        profile.best_times,
+        Some(vec![
+            BestTime {
+                board: "16x16".to_string(),
+                time: 48_000,
+            },
+            BestTime {
+                board: "9x9".to_string(),
+                time: 9_500,
+            },
+        ])
+    );
+}
+
+#[test]
+fn profile_card_grows_with_the_statistics() {
+    let name_only = PlayerProfile::new(UUID, Some("Alex1607".to_string()), None);
+    let with_games =
+        PlayerProfile::new(UUID, None, Some(vec![game("a", true, 12_000, Some("9x9"))]));
+
+    let (width, short) = card_size(&name_only);
+    let (_, tall) = card_size(&with_games);
+    assert_eq!(width, 360);
+    assert!(tall > short);
+}
+
+#[test]
+fn underscores_in_names_are_drawn() {
+    let card = |name: &str| PlayerProfile::new(UUID, Some(name.to_string()), None).render_card();
+
+    assert_ne!(card("Alex_1607").unwrap(), card("Alex?1607").unwrap());
+}
@@ -0,0 +1,40 @@
+use crate::analysis::replay::{losing_move, merge_moves};
+use crate::renderer::ProgressStyle;
+use crate::tests::{fixture, renderer};
+use crate::textures::load_textures;
+
+const LOST_V2: &str = include_str!("fixtures/lost_v2.txt");
+
+#[test]
+fn losing_click_is_found() {
+    let parsed_data = fixture(LOST_V2);
+    let moves = merge_moves(
+        &parsed_data.open_data,
+        &parsed_data.flag_data,
+        &parsed_data.chord_data,
+    );
+
+    let losing = losing_move(&parsed_data.game_board, &moves).expect("game should be lost");
+    assert_eq!((losing.x, losing.y, losing.total_time), (3, 0, 3));
+}
+
+#[test]
+fn timeline_marks_flags_and_the_losing_click() {
+    let mut renderer = renderer(LOST_V2, false).with_progress_style(ProgressStyle::Timeline);
+
+    let image = image::load_from_memory(&renderer.render_jpeg().unwrap())
+        .unwrap()
+        .to_rgba8();
+    assert_eq!(image.dimensions(), (4 * 32, 2 * 32 + 8));
+
+    let colours = load_textures(&false).progress;
+    assert_eq!(*image.get_pixel(0, 2 * 32), colours.flag);
+    assert_eq!(*image.get_pixel(4 * 32 - 1, 2 * 32 + 7), colours.mine);
+    assert_eq!(*image.get_pixel(64, 2 * 32), colours.done);
+}
+
+#[test]
+fn unknown_progress_style_is_rejected() {
+    assert!("cells".parse::<ProgressStyle>().is_ok());
+    assert!("bogus".parse::<ProgressStyle>().is_err());
+}
@@ -0,0 +1,209 @@
+use std::time::Duration;
+
+use crate::error::MinesweeperError;
+use crate::provider::config::{ConfigValues, ProviderConfig};
+use crate::provider::greev::greev_provider::{GreevConfig, GreevProvider};
+use crate::provider::http::{HttpResponse, MockClient};
+use crate::provider::mcplayhd::mcplay_provider::{
+    McPlayHdConfig, McPlayHdProvider, MCPLAYHD_API_KEY,
+};
+use crate::provider::provider::{EnumProviders, Provider};
+
+const GREEV_URL: &str = "https://api.greev.eu/v2/stats/minesweeper/game/abc";
+/// `zz` in base 36
+const MCPLAYHD_URL: &str = "https://mcplayhd.net/api/v1/minesweeper/game/1295";
+
+const GREEV_GAME: &str = r#"{
+    "gameData": "2=4x2,10+30+101;302+001P",
+    "type": "classic",
+    "time": 30,
+    "generator": "noGuess",
+    "uuid": "0c9bf2a2-1c1a-4a53-a6a4-3a3c1e2c1f5d",
+    "correctFlags": 0,
+    "incorrectFlags": 1,
+    "won": false
+}"#;
+
+const MCPLAYHD_GAME: &str = r#"{
+    "status": 200,
+    "data": {
+        "gameInfo": {
+            "id": 1295,
+            "uuid": "0c9bf2a2-1c1a-4a53-a6a4-3a3c1e2c1f5d",
+            "won": true,
+            "flagsCorrect": 10,
+            "flagsIncorrect": 0,
+            "timeStart": 1700000000000,
+            "timeEnd": 1700000012345,
+            "timeTaken": 12345,
+            "mines": 10,
+            "sizeX": 9,
+            "sizeZ": 9,
+            "algebraicNotation": "2=4x2,10+30+101;302+001P"
+        },
+        "players": [
+            {
+                "uuid": "0c9bf2a2-1c1a-4a53-a6a4-3a3c1e2c1f5d",
+                "name": "Alex1607",
+                "group": "default"
+            }
+        ]
+    }
+}"#;
+
+fn api_key(api_key: &str) -> ConfigValues {
+    let mut values = ConfigValues::default();
+    values.insert(MCPLAYHD_API_KEY, api_key.to_string());
+    values
+}
+
+#[test]
+fn greev_game_is_parsed() {
+    let client = MockClient::default().with_response(GREEV_URL, HttpResponse::new(200, GREEV_GAME));
+
+    let api_data =
+        pollster::block_on(GreevProvider.fetch_data(&client, "abc", &GreevConfig)).unwrap();
+    assert_eq!(
+        api_data.game_data.as_deref(),
+        Some("2=4x2,10+30+101;302+001P")
+    );
+    assert_eq!(api_data.generator.as_deref(), Some("noGuess"));
+    assert_eq!(api_data.incorrect_flags, Some(1));
+    assert_eq!(api_data.won, Some(false));
+}
+
+#[test]
+fn mcplayhd_authenticates_with_the_api_key() {
+    let client =
+        MockClient::default().with_response(MCPLAYHD_URL, HttpResponse::new(200, MCPLAYHD_GAME));
+
+    let config = McPlayHdConfig::from_values(&api_key("secret")).unwrap();
+    let api_data = pollster::block_on(McPlayHdProvider.fetch_data(&client, "zz", &config)).unwrap();
+    assert_eq!(api_data.time, Some(12345));
+    assert_eq!(api_data.correct_flags, Some(10));
+    assert_eq!(api_data.won, Some(true));
+
+    let requests = client.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].header("authorization"), Some("Bearer secret"));
+}
+
+#[test]
+fn mcplayhd_game_info_is_kept() {
+    let client =
+        MockClient::default().with_response(MCPLAYHD_URL, HttpResponse::new(200, MCPLAYHD_GAME));
+
+    let config = McPlayHdConfig::from_values(&api_key("secret")).unwrap();
+    let api_data = pollster::block_on(McPlayHdProvider.fetch_data(&client, "zz", &config)).unwrap();
+    assert_eq!(api_data.mines, Some(10));
+    assert_eq!((api_data.width, api_data.height), (Some(9), Some(9)));
+    assert_eq!(
+        (api_data.time_start, api_data.time_end),
+        (Some(1700000000000), Some(1700000012345))
+    );
+    assert_eq!(api_data.player_name(), Some("Alex1607"));
+}
+
+#[test]
+fn mcplayhd_without_api_key_sends_nothing() {
+    let client = MockClient::default();
+    let provider = EnumProviders::McPlayHd(McPlayHdProvider);
+    assert_eq!(provider.config_keys()[0].name, MCPLAYHD_API_KEY);
+
+    for config in [ConfigValues::default(), api_key("")] {
+        let result = pollster::block_on(provider.fetch_data(&client, "zz", &config));
+        assert!(matches!(
+            result,
+            Err(MinesweeperError::MissingConfig(MCPLAYHD_API_KEY))
+        ));
+    }
+    assert!(client.requests().is_empty());
+}
+
+#[test]
+fn broken_responses_are_errors() {
+    let client = MockClient::default().with_response(GREEV_URL, HttpResponse::new(200, "<html>"));
+
+    let result = pollster::block_on(GreevProvider.fetch_data(&client, "abc", &GreevConfig));
+    assert!(matches!(result, Err(MinesweeperError::ApiDataParse)));
+
+    let result = pollster::block_on(GreevProvider.fetch_data(&client, "unknown", &GreevConfig));
+    assert!(matches!(result, Err(MinesweeperError::UpstreamRequest)));
+}
+
+#[test]
+fn server_errors_are_retried_with_backoff() {
+    let client = MockClient::default()
+        .with_response(GREEV_URL, HttpResponse::new(503, ""))
+        .with_timeout(GREEV_URL)
+        .with_response(GREEV_URL, HttpResponse::new(200, GREEV_GAME));
+
+    let result = pollster::block_on(GreevProvider.fetch_data(&client, "abc", &GreevConfig));
+    assert!(result.is_ok());
+    assert_eq!(client.requests().len(), 3);
+    assert_eq!(
+        client.sleeps(),
+        [Duration::from_millis(250), Duration::from_millis(500)]
+    );
+}
+
+#[test]
+fn retries_are_bounded() {
+    let client = MockClient::default().with_response(GREEV_URL, HttpResponse::new(500, ""));
+
+    let result = pollster::block_on(GreevProvider.fetch_data(&client, "abc", &GreevConfig));
+    assert!(matches!(result, Err(MinesweeperError::UpstreamServer(500))));
+    assert_eq!(client.requests().len(), 3);
+}
+
+#[test]
+fn client_errors_are_not_retried() {
+    for (status, expected) in [
+        (404, MinesweeperError::UpstreamNotFound),
+        (401, MinesweeperError::UpstreamAuth),
+        (400, MinesweeperError::UpstreamStatus(400)),
+    ] {
+        let client = MockClient::default().with_response(GREEV_URL, HttpResponse::new(status, ""));
+
+        let result = pollster::block_on(GreevProvider.fetch_data(&client, "abc", &GreevConfig));
+        assert_eq!(
+            result.err().map(|err| err.to_string()),
+            Some(expected.to_string())
+        );
+        assert_eq!(client.requests().len(), 1);
+    }
+}
+
+#[test]
+fn rate_limits_respect_retry_after() {
+    let short = MockClient::default()
+        .with_response(
+            GREEV_URL,
+            HttpResponse::new(429, "").with_header("Retry-After", "1"),
+        )
+        .with_response(GREEV_URL, HttpResponse::new(200, GREEV_GAME));
+    assert!(pollster::block_on(GreevProvider.fetch_data(&short, "abc", &GreevConfig)).is_ok());
+    assert_eq!(short.sleeps(), [Duration::from_secs(1)]);
+
+    let long = MockClient::default().with_response(
+        GREEV_URL,
+        HttpResponse::new(429, "").with_header("retry-after", "60"),
+    );
+    let result = pollster::block_on(GreevProvider.fetch_data(&long, "abc", &GreevConfig));
+    assert!(matches!(
+        result,
+        Err(MinesweeperError::RateLimited {
+            retry_after: Some(60)
+        })
+    ));
+    assert!(long.sleeps().is_empty());
+}
+
+#[test]
+fn timeouts_are_reported() {
+    let client = MockClient::default().with_timeout(GREEV_URL);
+
+    let result = pollster::block_on(GreevProvider.fetch_data(&client, "abc", &GreevConfig));
+    assert!(matches!(result, Err(MinesweeperError::UpstreamTimeout)));
+    assert_eq!(client.requests().len(), 3);
+}
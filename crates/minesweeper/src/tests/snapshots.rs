@@ -0,0 +1,126 @@
+//! Replays every fixture of the corpus and compares the outcome against the golden files in
+//! `fixtures/snapshots`. After an intended change the golden files are rewritten with
+//! `UPDATE_SNAPSHOTS=1 cargo test`.
+
+use std::path::PathBuf;
+
+use crate::renderer::FrameSource;
+use crate::tests::renderer;
+use crate::text::TextStyle;
+
+/// Game data of both parser versions, named after their golden files
+const CORPUS: [(&str, &str); 7] = [
+    ("replay_v1_9x9", include_str!("fixtures/replay_v1_9x9.txt")),
+    ("tall_v1", include_str!("fixtures/tall_v1.txt")),
+    (
+        "replay_v2_16x16",
+        include_str!("fixtures/replay_v2_16x16.txt"),
+    ),
+    (
+        "replay_v2_30x16_lost",
+        include_str!("fixtures/replay_v2_30x16_lost.txt"),
+    ),
+    ("lost_v2", include_str!("fixtures/lost_v2.txt")),
+    ("wide_v2", include_str!("fixtures/wide_v2.txt")),
+    ("wide_v2_rival", include_str!("fixtures/wide_v2_rival.txt")),
+];
+
+/// FNV-1a, stable across platforms and compiler versions unlike the std hasher
+fn hash(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Hashes the decoded pixels, so a different compression of the same image still matches.
+fn png_hash(game_data: &str) -> String {
+    let png = renderer(game_data, false).render_jpeg().unwrap();
+    let image = image::load_from_memory(&png).unwrap().to_rgba8();
+
+    format!(
+        "png {}x{} {:016x}",
+        image.width(),
+        image.height(),
+        hash(image.as_raw())
+    )
+}
+
+/// Small fixtures whose whole gif gets encoded, so the encoder is covered as well
+const ENCODED_GIFS: [&str; 3] = ["tall_v1", "wide_v2", "lost_v2"];
+
+/// Amount of animation frames that get hashed for the other fixtures, the final board is already
+/// covered by the png
+const HASHED_FRAMES: usize = 8;
+
+/// Hashes the bytes of the encoded gif as they are streamed.
+fn encoded_gif_hash(game_data: &str) -> String {
+    let gif = renderer(game_data, true)
+        .into_gif_stream()
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap()
+        .concat();
+
+    format!("gif {} bytes {:016x}", gif.len(), hash(&gif))
+}
+
+/// Hashes the first frames as the replay produces them, encoding the whole gif of the big replays
+/// would dominate the test run.
+fn gif_hash(game_data: &str) -> String {
+    let mut frames = renderer(game_data, true).into_frames();
+    let hashed: Vec<_> = std::iter::from_fn(|| frames.next_frame())
+        .take(HASHED_FRAMES)
+        .map(|frame| frame.unwrap())
+        .collect();
+    let pixels: Vec<u8> = hashed
+        .iter()
+        .flat_map(|frame| {
+            let delay = (frame.delay.as_millis() as u64).to_le_bytes();
+            delay
+                .into_iter()
+                .chain(frame.image.as_raw().iter().copied())
+        })
+        .collect();
+
+    format!("gif first {} frames {:016x}", hashed.len(), hash(&pixels))
+}
+
+/// Compares against the golden file, or rewrites it when `UPDATE_SNAPSHOTS` is set.
+fn assert_snapshot(file_name: &str, actual: &str) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src/tests/fixtures/snapshots")
+        .join(file_name);
+
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+
+    let expected = std::fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!("{file_name} is missing, create it with UPDATE_SNAPSHOTS=1 cargo test")
+    });
+    assert_eq!(
+        expected, actual,
+        "{file_name} does not match, the replay changed"
+    );
+}
+
+#[test]
+fn board_snapshots() {
+    for (name, game_data) in CORPUS {
+        let board = renderer(game_data, false).render_text(TextStyle::Ascii);
+        assert_snapshot(&format!("{name}.board"), &board);
+    }
+}
+
+#[test]
+fn render_hashes() {
+    for (name, game_data) in CORPUS {
+        let gif = match ENCODED_GIFS.contains(&name) {
+            true => encoded_gif_hash(game_data),
+            false => gif_hash(game_data),
+        };
+        let hashes = format!("{}\n{}\n", png_hash(game_data), gif);
+        assert_snapshot(&format!("{name}.hashes"), &hashes);
+    }
+}
@@ -0,0 +1,103 @@
+use crate::analysis::classifier::{classify, Classification};
+use crate::analysis::replay::{merge_moves, Move};
+use crate::analysis::solver::{solve, CellKnowledge, Solver};
+use crate::tests::{board, fixture, flag, open};
+
+const REPLAY_V2_16X16: &str = include_str!("fixtures/replay_v2_16x16.txt");
+
+#[test]
+fn single_candidate_is_a_forced_mine() {
+    let knowledge = solve(&board(&["o*"]));
+
+    assert_eq!(knowledge.at(0, 0), CellKnowledge::Revealed);
+    assert_eq!(knowledge.at(1, 0), CellKnowledge::Mine);
+    assert_eq!(knowledge.chance_at(1, 0), 1.0);
+}
+
+#[test]
+fn one_one_pattern_proves_the_outer_cells_safe() {
+    // The left 1 holds the only mine the right 1 sees
+    let knowledge = solve(&board(&["*..", "oo."]));
+
+    assert_eq!(knowledge.at(2, 0), CellKnowledge::Safe);
+    assert_eq!(knowledge.at(2, 1), CellKnowledge::Safe);
+    assert_eq!(knowledge.at(0, 0), CellKnowledge::Unknown);
+    assert_eq!(knowledge.at(1, 0), CellKnowledge::Unknown);
+    assert!(knowledge.has_safe_cell());
+}
+
+#[test]
+fn exhausted_search_spreads_the_mines_evenly() {
+    // Every column of the two closed rows holds one mine in either row, which gives 2^40
+    // configurations, far more than the node budget allows to search
+    let row = |column: usize| -> String {
+        (0..60)
+            .map(|x| if x % 3 == column { '*' } else { '.' })
+            .collect()
+    };
+    let board = board(&[&row(0), &"o".repeat(60), &row(1)]);
+
+    let knowledge = solve(&board);
+    for y in [0, 2] {
+        for x in 0..60 {
+            assert_eq!(knowledge.at(x, y), CellKnowledge::Unknown);
+            assert_eq!(knowledge.chance_at(x, y), 40.0 / 120.0);
+        }
+    }
+}
+
+#[test]
+fn reused_components_match_a_fresh_solve() {
+    let parsed_data = fixture(REPLAY_V2_16X16);
+    let moves = merge_moves(
+        &parsed_data.open_data,
+        &parsed_data.flag_data,
+        &parsed_data.chord_data,
+    );
+
+    let mut board = parsed_data.game_board.clone();
+    let mut solver = Solver::default();
+    for mv in &moves {
+        mv.apply(&mut board);
+
+        let reused = solver.solve(&board);
+        let fresh = solve(&board);
+        assert_eq!(reused.cells, fresh.cells);
+        assert_eq!(reused.mine_chance, fresh.mine_chance);
+    }
+}
+
+#[test]
+fn opens_are_labelled_by_what_was_deducible() {
+    let board = board(&["*..", "oo."]);
+
+    let labels = |moves: &[Move]| -> Vec<Classification> {
+        classify(&board, moves)
+            .into_iter()
+            .map(|classified| classified.classification)
+            .collect()
+    };
+
+    assert_eq!(labels(&[open(2, 0, 0)]), [Classification::DeducibleSafe]);
+    assert_eq!(labels(&[open(1, 0, 0)]), [Classification::UnnecessaryRisk]);
+    assert_eq!(labels(&[open(0, 1, 0)]), [Classification::NoEffect]);
+    assert_eq!(
+        labels(&[flag(0, 0, 0), flag(1, 0, 0)]),
+        [Classification::Correct, Classification::Incorrect]
+    );
+}
+
+#[test]
+fn guesses_and_mistakes_are_told_apart() {
+    let fifty_fifty = board(&["*.", "oo"]);
+    let classified = classify(&fifty_fifty, &[open(1, 0, 0)]);
+    assert_eq!(classified[0].classification, Classification::Guess);
+    assert_eq!(classified[0].mine_chance, Some(0.5));
+
+    let forced = board(&["o*."]);
+    let classified = classify(&forced, &[open(1, 0, 0), open(2, 0, 0)]);
+    assert_eq!(classified[0].classification, Classification::Mistake);
+    assert!(classified[0].mine);
+    // Nothing happens after the game is lost
+    assert_eq!(classified[1].classification, Classification::NoEffect);
+}
@@ -0,0 +1,98 @@
+use crate::provider::provider::ApiData;
+use crate::stats::{GameStats, Mismatch, ReplayStats};
+use crate::tests::fixture;
+
+const LOST_V2: &str = include_str!("fixtures/lost_v2.txt");
+const REPLAY_V1_9X9: &str = include_str!("fixtures/replay_v1_9x9.txt");
+
+/// Api data of the fixture as the provider would report it, with extra fields appended
+fn api_data(extra: &str) -> ApiData {
+    serde_json::from_str(&format!(
+        r#"{{"uuid": "0c9bf2a2-1c1a-4a53-a6a4-3a3c1e2c1f5d", "time": 14000, "won": true{extra}}}"#
+    ))
+    .expect("api data should deserialize")
+}
+
+#[test]
+fn matching_game_info_has_no_mismatches() {
+    let parsed_data = fixture(REPLAY_V1_9X9);
+    let api_data = api_data(
+        r#", "mines": 10, "width": 9, "height": 9, "correctFlags": 7, "incorrectFlags": 0"#,
+    );
+
+    let stats = GameStats::new(&api_data, &parsed_data);
+    assert_eq!(stats.reported.mines, Some(10));
+    assert!(stats.mismatches.is_empty());
+}
+
+#[test]
+fn missing_game_info_is_not_checked() {
+    let parsed_data = fixture(REPLAY_V1_9X9);
+
+    let stats = GameStats::new(&api_data(""), &parsed_data);
+    assert!(stats.mismatches.is_empty());
+    let json = serde_json::to_value(&stats).unwrap();
+    assert!(json["reported"].get("mines").is_none());
+}
+
+#[test]
+fn provider_without_any_game_info_has_no_mismatches() {
+    let api_data: ApiData = serde_json::from_str(r#"{"uuid": ""}"#).unwrap();
+
+    for game_data in [REPLAY_V1_9X9, LOST_V2] {
+        let parsed_data = fixture(game_data);
+        let stats = GameStats::new(&api_data, &parsed_data);
+        assert!(stats.mismatches.is_empty(), "{:?}", stats.mismatches);
+        assert_eq!((stats.reported.time, stats.reported.won), (None, None));
+    }
+}
+
+#[test]
+fn differing_game_info_is_reported() {
+    let parsed_data = fixture(REPLAY_V1_9X9);
+    let api_data = api_data(r#", "mines": 12, "width": 16, "height": 9"#);
+
+    let stats = GameStats::new(&api_data, &parsed_data);
+    assert_eq!(
+        stats.mismatches,
+        vec![
+            Mismatch {
+                field: "mines",
+                reported: 12.into(),
+                replay: 10.into(),
+            },
+            Mismatch {
+                field: "width",
+                reported: 16.into(),
+                replay: 9.into(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn differing_results_are_reported() {
+    let parsed_data = fixture(REPLAY_V1_9X9);
+    let api_data: ApiData = serde_json::from_str(
+        r#"{"uuid": "0c9bf2a2-1c1a-4a53-a6a4-3a3c1e2c1f5d", "time": 30000, "won": false,
+            "correctFlags": 10, "incorrectFlags": 0}"#,
+    )
+    .unwrap();
+
+    let fields: Vec<&str> = GameStats::new(&api_data, &parsed_data)
+        .mismatches
+        .iter()
+        .map(|mismatch| mismatch.field)
+        .collect();
+    assert_eq!(fields, vec!["correctFlags", "won", "time"]);
+}
+
+#[test]
+fn replay_flags_are_counted() {
+    let parsed_data = fixture(REPLAY_V1_9X9);
+
+    let replay = ReplayStats::new(&parsed_data);
+    assert_eq!((replay.correct_flags, replay.incorrect_flags), (7, 0));
+    assert_eq!(replay.duration, 13400);
+    assert!(replay.won);
+}
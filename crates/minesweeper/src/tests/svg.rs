@@ -0,0 +1,43 @@
+use crate::renderer::ProgressStyle;
+use crate::tests::renderer;
+
+const LOST_V2: &str = include_str!("fixtures/lost_v2.txt");
+
+fn render_svg(progress_style: ProgressStyle) -> String {
+    renderer(LOST_V2, false)
+        .with_progress_style(progress_style)
+        .render_svg()
+}
+
+#[test]
+fn svg_contains_every_cell() {
+    let svg = render_svg(ProgressStyle::Ticks);
+
+    assert!(svg.starts_with("<svg "));
+    assert!(svg.contains(r#"viewBox="0 0 128 68""#));
+    assert!(svg.trim_end().ends_with("</svg>"));
+
+    let cells = ["closed", "open", "exploded"]
+        .iter()
+        .map(|class| svg.matches(&format!(r#"<rect class="{class}""#)).count())
+        .sum::<usize>();
+    assert_eq!(cells, 4 * 2);
+    assert_eq!(svg.matches(r#"<circle class="mine""#).count(), 1);
+    assert_eq!(svg.matches(r#"<polygon class="flag""#).count(), 1);
+}
+
+#[test]
+fn svg_numbers_are_text() {
+    let svg = render_svg(ProgressStyle::Timeline);
+
+    let numbers: Vec<&str> = svg
+        .split("<text class=\"n")
+        .skip(1)
+        .map(|text| &text[text.find('>').unwrap() + 1..text.find('<').unwrap()])
+        .collect();
+    assert!(!numbers.is_empty());
+    assert!(numbers
+        .iter()
+        .all(|number| (1..=8).contains(&number.parse::<u8>().unwrap())));
+    assert!(svg.contains(r#"height="8""#));
+}
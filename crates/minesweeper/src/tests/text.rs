@@ -0,0 +1,25 @@
+use crate::renderer::RenderType;
+use crate::tests::renderer;
+use crate::text::TextStyle;
+
+const LOST_V2: &str = include_str!("fixtures/lost_v2.txt");
+
+fn render_text(style: TextStyle) -> String {
+    renderer(LOST_V2, false).render_text(style)
+}
+
+#[test]
+fn ascii_board() {
+    assert_eq!(render_text(TextStyle::Ascii), "F.1*\n..1#\n");
+}
+
+#[test]
+fn emoji_board() {
+    assert_eq!(render_text(TextStyle::Emoji), "🚩⬜1️⃣💣\n⬜⬜1️⃣🟦\n");
+}
+
+#[test]
+fn text_formats_are_parsed() {
+    assert!("txt".parse::<RenderType>().unwrap() == RenderType::Text(TextStyle::Ascii));
+    assert!("emoji".parse::<RenderType>().unwrap() == RenderType::Text(TextStyle::Emoji));
+}
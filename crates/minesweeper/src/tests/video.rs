@@ -0,0 +1,98 @@
+use crate::output::RenderOutput;
+use crate::renderer::RenderType;
+use crate::tests::renderer;
+use crate::video::AviStream;
+
+const LOST_V2: &str = include_str!("fixtures/lost_v2.txt");
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+#[test]
+fn video_is_a_complete_avi() {
+    let renderer = renderer(LOST_V2, true);
+
+    let video = RenderOutput::Video(AviStream::new(renderer.into_frames()).unwrap())
+        .into_bytes()
+        .unwrap();
+
+    assert_eq!(&video[0..4], b"RIFF");
+    assert_eq!(read_u32(&video, 4) as usize, video.len() - 8);
+    assert_eq!(&video[8..12], b"AVI ");
+
+    // The main header follows the "hdrl" list identifier
+    assert_eq!(&video[24..28], b"avih");
+    let total_frames = read_u32(&video, 32 + 16);
+    assert!(total_frames > 0);
+    assert_eq!(
+        (read_u32(&video, 32 + 32), read_u32(&video, 32 + 36)),
+        (4 * 32, 2 * 32 + 4)
+    );
+
+    let index = video.len() - 8 - 16 * total_frames as usize;
+    assert_eq!(&video[index..index + 4], b"idx1");
+
+    let movi = video.windows(4).position(|id| id == b"movi").unwrap();
+    let first_frame = movi + 4 + read_u32(&video, index + 16) as usize;
+    assert_eq!(&video[first_frame - 4..first_frame], b"00dc");
+    let frame_size = read_u32(&video, index + 20) as usize;
+    let frame =
+        image::load_from_memory(&video[first_frame + 4..first_frame + 4 + frame_size]).unwrap();
+    assert_eq!((frame.width(), frame.height()), (4 * 32, 2 * 32 + 4));
+}
+
+#[test]
+fn streamed_video_has_its_sizes_up_front() {
+    let renderer = renderer(LOST_V2, true);
+
+    let parts: Vec<Vec<u8>> = AviStream::new(renderer.into_frames())
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    let video = parts.concat();
+
+    // The headers are the first part, so they are sent before any frame got compressed again
+    let header = &parts[0];
+    assert_eq!(read_u32(header, 4) as usize, video.len() - 8);
+    let index = video.len() - parts.last().unwrap().len();
+    assert_eq!(&video[index..index + 4], b"idx1");
+    let total_frames = read_u32(header, 32 + 16);
+    assert_eq!(read_u32(&video, index + 4), 16 * total_frames);
+
+    let movi_size = read_u32(header, header.len() - 8) as usize;
+    assert_eq!(header.len() - 4 + movi_size, index);
+}
+
+#[test]
+fn held_frames_are_dropped_frames() {
+    let renderer = renderer(LOST_V2, true);
+
+    let parts: Vec<Vec<u8>> = AviStream::new(renderer.into_frames())
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    let video = parts.concat();
+    let index = video.len() - parts.last().unwrap().len();
+    let entries: Vec<(u32, u32)> = video[index + 8..]
+        .chunks(16)
+        .map(|entry| (read_u32(entry, 4), read_u32(entry, 12)))
+        .collect();
+
+    // Every compressed frame is written once, the video frames it is held for are empty
+    let compressed = entries.iter().filter(|(_, size)| *size > 0).count();
+    assert_eq!(compressed, parts.len() - 2);
+    assert!(entries.len() > compressed);
+    for (flags, size) in entries {
+        assert_eq!(flags == 0x10, size > 0);
+    }
+}
+
+#[test]
+fn format_selects_the_render_type() {
+    assert!("avi".parse::<RenderType>().unwrap() == RenderType::Video);
+    assert!("png".parse::<RenderType>().unwrap() == RenderType::Image);
+    assert!("mp3".parse::<RenderType>().is_err());
+    // No webm is encoded, so asking for it is an error instead of getting an avi
+    assert!("webm".parse::<RenderType>().is_err());
+}
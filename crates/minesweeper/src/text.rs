@@ -0,0 +1,47 @@
+use crate::minesweeper_logic::{Board, Field, FieldState};
+
+/// Characters a board is written with.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum TextStyle {
+    /// Numbers, `F` for flags, `?` for unsure flags, `*` for opened mines, `#` for closed and `.` for empty cells
+    Ascii,
+    /// Emoji which chat clients render as a grid of squares
+    Emoji,
+}
+
+const KEYCAPS: [&str; 9] = ["⬜", "1️⃣", "2️⃣", "3️⃣", "4️⃣", "5️⃣", "6️⃣", "7️⃣", "8️⃣"];
+
+/// Writes the current state of the board, one line per row.
+pub fn render_text(board: &Board, style: TextStyle) -> String {
+    board
+        .fields
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|field| cell(field, style))
+                .collect::<String>()
+                + "\n"
+        })
+        .collect()
+}
+
+fn cell(field: &Field, style: TextStyle) -> String {
+    match style {
+        TextStyle::Ascii => match field.field_state {
+            FieldState::Closed => "#".to_string(),
+            FieldState::Flagged => "F".to_string(),
+            FieldState::UnsureFlagged => "?".to_string(),
+            FieldState::Open if field.mine => "*".to_string(),
+            FieldState::Open if field.value == 0 => ".".to_string(),
+            FieldState::Open => field.value.to_string(),
+        },
+        TextStyle::Emoji => match field.field_state {
+            FieldState::Closed => "🟦",
+            FieldState::Flagged => "🚩",
+            FieldState::UnsureFlagged => "❓",
+            FieldState::Open if field.mine => "💣",
+            FieldState::Open => KEYCAPS[field.value as usize],
+        }
+        .to_string(),
+    }
+}
@@ -0,0 +1,47 @@
+use image::Rgba;
+
+pub struct TexturePack {
+    pub sprites: Vec<u8>,
+    pub progress: ProgressColours,
+}
+
+/// Colours of the progress indicator below the board, chosen to match the texture pack.
+#[derive(Clone, Copy)]
+pub struct ProgressColours {
+    pub done: Rgba<u8>,
+    pub left: Rgba<u8>,
+    /// Timeline marker of a placed flag
+    pub flag: Rgba<u8>,
+    /// Timeline marker of the click that hit a mine
+    pub mine: Rgba<u8>,
+}
+
+impl ProgressColours {
+    pub fn all(&self) -> [Rgba<u8>; 4] {
+        [self.done, self.left, self.flag, self.mine]
+    }
+}
+
+pub fn load_textures(use_gif: &bool) -> TexturePack {
+    if *use_gif {
+        TexturePack {
+            sprites: include_bytes!("../resources/skin_20.png").to_vec(),
+            progress: ProgressColours {
+                done: Rgba([103, 149, 60, 255]),
+                left: Rgba([0, 0, 0, 255]),
+                flag: Rgba([230, 200, 40, 255]),
+                mine: Rgba([220, 40, 40, 255]),
+            },
+        }
+    } else {
+        TexturePack {
+            sprites: include_bytes!("../resources/skin_full.png").to_vec(),
+            progress: ProgressColours {
+                done: Rgba([103, 149, 60, 255]),
+                left: Rgba([0, 0, 0, 255]),
+                flag: Rgba([255, 214, 51, 255]),
+                mine: Rgba([235, 51, 36, 255]),
+            },
+        }
+    }
+}
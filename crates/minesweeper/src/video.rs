@@ -0,0 +1,318 @@
+use std::time::Duration;
+
+use image::codecs::jpeg::JpegEncoder;
+use image::{DynamicImage, ExtendedColorType, ImageBuffer, Rgba};
+
+use crate::error::MinesweeperError;
+use crate::renderer::FrameSource;
+
+/// Frames per second of the video, the delays of the animation get rounded to whole frames
+const FPS: u32 = 10;
+const JPEG_QUALITY: u8 = 80;
+/// Longest time a single frame is shown, a gif holds its last frame for 15 seconds which is too long for a clip
+const MAX_FRAME_HOLD: Duration = Duration::from_secs(3);
+/// Flag of an index entry for a frame that can be decoded on its own
+const AVIIF_KEYFRAME: u32 = 0x10;
+/// Flag of the main header telling that the file contains an index
+const AVIF_HASINDEX: u32 = 0x10;
+
+/// A motion JPEG video in an AVI container. The frames are compressed once up front to learn their
+/// sizes for the headers and again while the video gets read, so only the sizes and the index
+/// entries are kept in memory.
+pub struct AviStream {
+    frames: VideoFrames,
+    width: u32,
+    height: u32,
+    /// Every compressed frame in order, worked out before the first byte is streamed
+    schedule: Vec<ScheduledFrame>,
+    /// Amount of scheduled frames written so far
+    written: usize,
+    /// Entries of the "idx1" chunk for every frame written so far
+    index: Vec<u8>,
+    /// Size of the "movi" list so far, starting with its identifier
+    movi_size: u32,
+    position: AviPosition,
+}
+
+/// A compressed frame and how many video frames it is shown for
+struct ScheduledFrame {
+    size: u32,
+    repeat: u32,
+}
+
+enum AviPosition {
+    Header,
+    /// Frames are written until the animation is over, followed by the index
+    Frames,
+    Done,
+}
+
+/// Draws the animation frames on top of each other and compresses the result whenever a video
+/// frame is due.
+struct VideoFrames {
+    source: Box<dyn FrameSource>,
+    /// Frames only contain the pixels that changed, so they are drawn on top of each other
+    canvas: ImageBuffer<Rgba<u8>, Vec<u8>>,
+    elapsed: Duration,
+    /// Amount of video frames covered so far
+    total_frames: u32,
+}
+
+impl VideoFrames {
+    fn new(source: impl FrameSource + 'static) -> VideoFrames {
+        let (width, height) = source.dimensions();
+
+        VideoFrames {
+            source: Box::new(source),
+            canvas: ImageBuffer::from_pixel(width, height, Rgba([0, 0, 0, 255])),
+            elapsed: Duration::ZERO,
+            total_frames: 0,
+        }
+    }
+
+    /// Draws animation frames until the next video frame is due and compresses it, along with the
+    /// amount of video frames it is shown for. Returns `None` once the animation is over.
+    fn next_frame(&mut self) -> Option<Result<(Vec<u8>, u32), MinesweeperError>> {
+        while let Some(frame) = self.source.next_frame() {
+            let frame = match frame {
+                Ok(frame) => frame,
+                Err(err) => return Some(Err(err)),
+            };
+            for (pixel, new) in self.canvas.pixels_mut().zip(frame.image.pixels()) {
+                if new.0[3] != 0 {
+                    *pixel = *new;
+                }
+            }
+
+            self.elapsed += frame.delay.min(MAX_FRAME_HOLD);
+
+            // Rounding the total instead of every single delay keeps the video in sync
+            let target = (self.elapsed.as_millis() as u64 * FPS as u64).div_ceil(1000) as u32;
+            let repeat = target.saturating_sub(self.total_frames);
+            if repeat > 0 {
+                self.total_frames = target;
+                return Some(encode_jpeg(&self.canvas).map(|data| (data, repeat)));
+            }
+        }
+
+        // A video needs at least one frame, even if the animation was too short for it
+        (self.total_frames == 0).then(|| {
+            self.total_frames = 1;
+            encode_jpeg(&self.canvas).map(|data| (data, 1))
+        })
+    }
+}
+
+impl AviStream {
+    /// Compresses every frame of a copy of the source to know the sizes in the headers.
+    pub fn new(source: impl FrameSource + Clone + 'static) -> Result<AviStream, MinesweeperError> {
+        let (width, height) = source.dimensions();
+
+        let mut planned = VideoFrames::new(source.clone());
+        let mut schedule = Vec::new();
+        while let Some(frame) = planned.next_frame() {
+            let (data, repeat) = frame?;
+            schedule.push(ScheduledFrame {
+                size: data.len() as u32,
+                repeat,
+            });
+        }
+
+        Ok(AviStream {
+            frames: VideoFrames::new(source),
+            width,
+            height,
+            schedule,
+            written: 0,
+            index: Vec::new(),
+            movi_size: 4,
+            position: AviPosition::Header,
+        })
+    }
+
+    /// Size of a frame chunk including its header and padding
+    fn chunk_size(size: u32) -> u32 {
+        8 + size.next_multiple_of(2)
+    }
+
+    /// Writes the chunk of a compressed frame, followed by empty chunks for the video frames it is
+    /// held for, which players show as dropped frames repeating the previous one.
+    fn write_frame(&mut self, data: &[u8], repeat: u32) -> Vec<u8> {
+        let mut bytes = chunk(b"00dc", data);
+        self.push_index(AVIIF_KEYFRAME, data.len() as u32);
+        for _ in 1..repeat {
+            bytes.extend(chunk(b"00dc", &[]));
+            self.push_index(0, 0);
+        }
+        bytes
+    }
+
+    fn push_index(&mut self, flags: u32, size: u32) {
+        // Offsets are relative to the "movi" identifier
+        self.index.extend(b"00dc");
+        push_u32(&mut self.index, flags);
+        push_u32(&mut self.index, self.movi_size);
+        push_u32(&mut self.index, size);
+        self.movi_size += Self::chunk_size(size);
+    }
+
+    /// Compresses the next scheduled frame, which has to come out the same as up front.
+    fn next_frame(&mut self) -> Result<Option<Vec<u8>>, MinesweeperError> {
+        let frame = self.frames.next_frame().transpose()?;
+        let scheduled = self.schedule.get(self.written);
+
+        match (frame, scheduled) {
+            (None, None) => Ok(None),
+            (Some((data, repeat)), Some(scheduled))
+                if data.len() as u32 == scheduled.size && repeat == scheduled.repeat =>
+            {
+                self.written += 1;
+                Ok(Some(self.write_frame(&data, repeat)))
+            }
+            _ => Err(MinesweeperError::VideoEncoding),
+        }
+    }
+
+    /// Headers up to the start of the first frame.
+    fn header(&self) -> Vec<u8> {
+        let total_frames: u32 = self.schedule.iter().map(|frame| frame.repeat).sum();
+        let largest_frame = self
+            .schedule
+            .iter()
+            .map(|frame| Self::chunk_size(frame.size))
+            .max()
+            .unwrap_or(0);
+        let movi_size = 4 + self
+            .schedule
+            .iter()
+            .map(|frame| Self::chunk_size(frame.size) + (frame.repeat - 1) * Self::chunk_size(0))
+            .sum::<u32>();
+        let index_size = 16 * total_frames;
+
+        let mut avih = Vec::with_capacity(56);
+        push_u32(&mut avih, 1_000_000 / FPS);
+        push_u32(&mut avih, largest_frame * FPS);
+        push_u32(&mut avih, 0);
+        push_u32(&mut avih, AVIF_HASINDEX);
+        push_u32(&mut avih, total_frames);
+        push_u32(&mut avih, 0);
+        push_u32(&mut avih, 1);
+        push_u32(&mut avih, largest_frame);
+        push_u32(&mut avih, self.width);
+        push_u32(&mut avih, self.height);
+        avih.extend([0; 16]);
+
+        let mut strh = Vec::with_capacity(56);
+        strh.extend(b"vidsMJPG");
+        push_u32(&mut strh, 0);
+        push_u32(&mut strh, 0);
+        push_u32(&mut strh, 0);
+        push_u32(&mut strh, 1);
+        push_u32(&mut strh, FPS);
+        push_u32(&mut strh, 0);
+        push_u32(&mut strh, total_frames);
+        push_u32(&mut strh, largest_frame);
+        push_u32(&mut strh, u32::MAX);
+        push_u32(&mut strh, 0);
+        push_u16(&mut strh, 0);
+        push_u16(&mut strh, 0);
+        push_u16(&mut strh, self.width as u16);
+        push_u16(&mut strh, self.height as u16);
+
+        let mut strf = Vec::with_capacity(40);
+        push_u32(&mut strf, 40);
+        push_u32(&mut strf, self.width);
+        push_u32(&mut strf, self.height);
+        push_u16(&mut strf, 1);
+        push_u16(&mut strf, 24);
+        strf.extend(b"MJPG");
+        push_u32(&mut strf, self.width * self.height * 3);
+        strf.extend([0; 16]);
+
+        let strl = list(
+            b"strl",
+            &[chunk(b"strh", &strh), chunk(b"strf", &strf)].concat(),
+        );
+        let hdrl = list(b"hdrl", &[chunk(b"avih", &avih), strl].concat());
+
+        let mut header = Vec::new();
+        header.extend(b"RIFF");
+        push_u32(
+            &mut header,
+            4 + hdrl.len() as u32 + 8 + movi_size + 8 + index_size,
+        );
+        header.extend(b"AVI ");
+        header.extend(hdrl);
+        header.extend(b"LIST");
+        push_u32(&mut header, movi_size);
+        header.extend(b"movi");
+        header
+    }
+}
+
+/// Every item holds a part of the file: the headers, a single compressed frame with the dropped
+/// frames after it and finally the index.
+impl Iterator for AviStream {
+    type Item = Result<Vec<u8>, MinesweeperError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Encoding stops after an error, as the position is only restored on success
+        let result = match std::mem::replace(&mut self.position, AviPosition::Done) {
+            AviPosition::Header => {
+                self.position = AviPosition::Frames;
+                Ok(self.header())
+            }
+            AviPosition::Frames => match self.next_frame() {
+                Ok(Some(bytes)) => {
+                    self.position = AviPosition::Frames;
+                    Ok(bytes)
+                }
+                Ok(None) => Ok(chunk(b"idx1", &self.index)),
+                Err(err) => Err(err),
+            },
+            AviPosition::Done => return None,
+        };
+
+        Some(result)
+    }
+}
+
+fn encode_jpeg(canvas: &ImageBuffer<Rgba<u8>, Vec<u8>>) -> Result<Vec<u8>, MinesweeperError> {
+    let rgb = DynamicImage::ImageRgba8(canvas.clone()).into_rgb8();
+
+    let mut data = Vec::new();
+    JpegEncoder::new_with_quality(&mut data, JPEG_QUALITY)
+        .encode(
+            rgb.as_raw(),
+            rgb.width(),
+            rgb.height(),
+            ExtendedColorType::Rgb8,
+        )
+        .map_err(|_| MinesweeperError::VideoEncoding)?;
+
+    Ok(data)
+}
+
+/// A RIFF chunk, padded to an even size
+fn chunk(id: &[u8; 4], data: &[u8]) -> Vec<u8> {
+    let mut chunk = Vec::with_capacity(8 + data.len() + 1);
+    chunk.extend(id);
+    push_u32(&mut chunk, data.len() as u32);
+    chunk.extend(data);
+    if data.len() % 2 == 1 {
+        chunk.push(0);
+    }
+    chunk
+}
+
+fn list(id: &[u8; 4], data: &[u8]) -> Vec<u8> {
+    chunk(b"LIST", &[id.as_slice(), data].concat())
+}
+
+fn push_u32(buffer: &mut Vec<u8>, value: u32) {
+    buffer.extend(value.to_le_bytes());
+}
+
+fn push_u16(buffer: &mut Vec<u8>, value: u16) {
+    buffer.extend(value.to_le_bytes());
+}
@@ -0,0 +1,99 @@
+use std::io::{ErrorKind, Read};
+use std::time::Duration;
+
+use minesweeper::error::MinesweeperError;
+use minesweeper::provider::config::{ConfigKey, ConfigValues, ProviderConfig};
+use minesweeper::provider::custom::custom_provider::CustomConfig;
+use minesweeper::provider::greev::greev_provider::GreevProvider;
+use minesweeper::provider::http::{HttpClient, HttpRequest, HttpResponse};
+use minesweeper::provider::mcplayhd::mcplay_provider::McPlayHdProvider;
+use minesweeper::provider::provider::{ApiData, EnumProviders, Provider};
+
+/// Sends the requests of the providers with a blocking native client.
+struct UreqClient;
+
+impl HttpClient for UreqClient {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse, MinesweeperError> {
+        let mut ureq_request = ureq::get(&request.url).timeout(request.timeout);
+        for (name, value) in &request.headers {
+            ureq_request = ureq_request.set(name, value);
+        }
+
+        // Error statuses are handed to the provider like any other response
+        let response = match ureq_request.call() {
+            Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+            Err(ureq::Error::Transport(transport)) => {
+                let timed_out = std::error::Error::source(&transport)
+                    .and_then(|source| source.downcast_ref::<std::io::Error>())
+                    .is_some_and(|err| {
+                        matches!(err.kind(), ErrorKind::TimedOut | ErrorKind::WouldBlock)
+                    });
+                return Err(match timed_out {
+                    true => MinesweeperError::UpstreamTimeout,
+                    false => MinesweeperError::UpstreamRequest,
+                });
+            }
+        };
+
+        let status = response.status();
+        let headers = response
+            .headers_names()
+            .into_iter()
+            .filter_map(|name| {
+                let value = response.header(&name)?.to_string();
+                Some((name, value))
+            })
+            .collect();
+        let mut body = Vec::new();
+        response
+            .into_reader()
+            .read_to_end(&mut body)
+            .map_err(|_| MinesweeperError::UpstreamRequest)?;
+
+        Ok(HttpResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        std::thread::sleep(duration);
+    }
+}
+
+/// Fetches a game from the provider with the given id, the same way the Worker does.
+pub fn fetch_game(provider_id: &str, game_id: &str) -> Result<ApiData, String> {
+    let mut providers = vec![
+        EnumProviders::Greev(GreevProvider),
+        EnumProviders::McPlayHd(McPlayHdProvider),
+    ];
+    match CustomConfig::from_values(&config_values(CustomConfig::KEYS)) {
+        Ok(config) => providers.extend(config.providers().map(EnumProviders::Custom)),
+        Err(MinesweeperError::MissingConfig(_)) => {}
+        Err(err) => return Err(err.to_string()),
+    }
+    let provider = providers
+        .iter()
+        .find(|provider| provider.id() == provider_id)
+        .ok_or(format!("Unknown provider {provider_id}"))?;
+    let config = config_values(provider.config_keys());
+
+    pollster::block_on(provider.fetch_data(&UreqClient, game_id, &config)).map_err(|err| {
+        format!(
+            "Unable to fetch game {game_id} from {} because of {err}",
+            provider.name()
+        )
+    })
+}
+
+/// Secrets and variables of the Worker are both read from environment variables of the same name
+fn config_values(keys: &[ConfigKey]) -> ConfigValues {
+    let mut config = ConfigValues::default();
+    for key in keys {
+        if let Ok(value) = std::env::var(key.name) {
+            config.insert(key.name, value);
+        }
+    }
+    config
+}
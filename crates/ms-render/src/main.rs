@@ -0,0 +1,232 @@
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::Parser;
+
+use minesweeper::analysis::overlay::OverlayType;
+use minesweeper::output::{render, RenderOptions};
+use minesweeper::parsers::parser::{parse_game_data, Generator, ParsedData};
+use minesweeper::provider::provider::ApiData;
+use minesweeper::renderer::{ProgressStyle, RenderType};
+use minesweeper::stats::{GameStats, ReplayStats};
+
+mod fetch;
+#[cfg(test)]
+mod tests;
+
+/// Renders minesweeper replays to disk, with the same options as the render route of the Worker.
+#[derive(Parser)]
+#[command(name = "ms-render", version)]
+struct Args {
+    /// Raw game data, e.g. `2=4x2,10+30+101;302+001P`
+    #[arg(long)]
+    data: Option<String>,
+    /// Files containing one game per line
+    #[arg(long, num_args = 1..)]
+    file: Vec<PathBuf>,
+    /// Provider to fetch the games from, `greev`, `mcplayhd` or the id of a custom provider
+    #[arg(long, requires = "game_id")]
+    provider: Option<String>,
+    /// Ids of the games to fetch from the provider
+    #[arg(long, num_args = 1.., requires = "provider")]
+    game_id: Vec<String>,
+    /// png, gif, avi, svg, txt, emoji or json for the stats of the replay
+    #[arg(long, default_value = "png")]
+    format: Format,
+    /// Shorthand for `--format gif`
+    #[arg(long)]
+    gif: bool,
+    /// Tints the cells, `solver` or `probability`
+    #[arg(long)]
+    overlay: Option<OverlayType>,
+    /// What the indicator below the board shows: ticks, cells, time or timeline
+    #[arg(long, default_value = "ticks")]
+    progress: ProgressStyle,
+    /// File to write to, `-` for stdout. A directory when rendering several games
+    #[arg(short, long)]
+    output: PathBuf,
+}
+
+#[derive(Copy, Clone)]
+enum Format {
+    Render(RenderType),
+    Json,
+}
+
+impl std::str::FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_ref() {
+            "json" => Ok(Format::Json),
+            _ => s.parse().map(Format::Render),
+        }
+    }
+}
+
+/// A game to render, named after where it came from.
+struct Game {
+    name: String,
+    game_data: String,
+    /// Data reported by the provider, only present for fetched games
+    api_data: Option<ApiData>,
+}
+
+fn main() -> ExitCode {
+    match run(Args::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: Args) -> Result<(), String> {
+    let games = collect_games(&args)?;
+    let format = match args.format {
+        Format::Render(RenderType::Image) if args.gif => Format::Render(RenderType::Gif),
+        format => format,
+    };
+
+    let single = games.len() == 1;
+    if !single {
+        fs::create_dir_all(&args.output)
+            .map_err(|err| format!("Unable to create {}: {err}", args.output.display()))?;
+    }
+
+    let total = games.len();
+    let mut failed = 0;
+    for game in games {
+        let result = encode(&game, format, &args).and_then(|(data, extension)| {
+            let path = if single {
+                args.output.clone()
+            } else {
+                args.output.join(format!("{}.{extension}", game.name))
+            };
+            write(&path, &data)
+        });
+
+        if let Err(message) = result {
+            eprintln!("{}: {message}", game.name);
+            failed += 1;
+        }
+    }
+
+    match failed {
+        0 => Ok(()),
+        _ => Err(format!("{failed} of {total} games could not be rendered")),
+    }
+}
+
+fn collect_games(args: &Args) -> Result<Vec<Game>, String> {
+    let mut games = Vec::new();
+
+    if let Some(game_data) = &args.data {
+        games.push(Game {
+            name: "game".to_string(),
+            game_data: game_data.clone(),
+            api_data: None,
+        });
+    }
+
+    for path in &args.file {
+        let content = fs::read_to_string(path)
+            .map_err(|err| format!("Unable to read {}: {err}", path.display()))?;
+        let stem = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| "game".to_string());
+
+        let lines: Vec<&str> = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect();
+        for (index, line) in lines.iter().enumerate() {
+            games.push(Game {
+                name: match lines.len() {
+                    1 => stem.clone(),
+                    _ => format!("{stem}-{}", index + 1),
+                },
+                game_data: line.to_string(),
+                api_data: None,
+            });
+        }
+    }
+
+    if let Some(provider) = &args.provider {
+        for game_id in &args.game_id {
+            let api_data = fetch::fetch_game(provider, game_id)?;
+            let Some(game_data) = api_data.game_data.clone() else {
+                return Err(format!("Game {game_id} does not contain any game data"));
+            };
+            games.push(Game {
+                name: game_id.clone(),
+                game_data,
+                api_data: Some(api_data),
+            });
+        }
+    }
+
+    if games.is_empty() {
+        return Err("No game given, use --data, --file or --provider with --game-id".to_string());
+    }
+
+    Ok(games)
+}
+
+/// Renders a single game, returning the encoded data and its file extension.
+fn encode(game: &Game, format: Format, args: &Args) -> Result<(Vec<u8>, &'static str), String> {
+    let parsed_data = parse(game)?;
+
+    match format {
+        Format::Json => {
+            let json = match &game.api_data {
+                Some(api_data) => {
+                    serde_json::to_vec_pretty(&GameStats::new(api_data, &parsed_data))
+                }
+                None => serde_json::to_vec_pretty(&ReplayStats::new(&parsed_data)),
+            }
+            .map_err(|err| err.to_string())?;
+            Ok((json, "json"))
+        }
+        Format::Render(render_type) => {
+            let options = RenderOptions {
+                render_type,
+                overlay: args.overlay,
+                progress_style: args.progress,
+            };
+            let output = render(parsed_data, options).map_err(|err| err.to_string())?;
+            let extension = output.extension();
+            Ok((
+                output.into_bytes().map_err(|err| err.to_string())?,
+                extension,
+            ))
+        }
+    }
+}
+
+fn parse(game: &Game) -> Result<ParsedData, String> {
+    let generator = game
+        .api_data
+        .as_ref()
+        .and_then(|api_data| api_data.generator.as_deref())
+        .map(Generator::from);
+
+    parse_game_data(&game.game_data)
+        .map(|parsed_data| parsed_data.with_generator(generator))
+        .map_err(|err| format!("Unable to parse game data because of {err}"))
+}
+
+fn write(path: &Path, data: &[u8]) -> Result<(), String> {
+    if path == Path::new("-") {
+        return std::io::stdout()
+            .write_all(data)
+            .map_err(|err| err.to_string());
+    }
+
+    fs::write(path, data).map_err(|err| format!("Unable to write {}: {err}", path.display()))
+}
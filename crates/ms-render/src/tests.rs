@@ -0,0 +1,155 @@
+use clap::Parser;
+
+use minesweeper::error::MinesweeperError;
+use minesweeper::parsers::parser::parse_game_data;
+
+use crate::{collect_games, encode, run, Args, Format};
+
+const LOST_V2: &str = "2=4x2,10+30+101;302+001P";
+
+#[test]
+fn files_with_several_games_get_numbered() {
+    let dir = std::env::temp_dir().join(format!("ms-render-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let single = dir.join("single.txt");
+    let archive = dir.join("archive.txt");
+    std::fs::write(&single, LOST_V2).unwrap();
+    std::fs::write(&archive, format!("{LOST_V2}\n\n{LOST_V2}\n")).unwrap();
+
+    let args = Args::parse_from([
+        "ms-render",
+        "--file",
+        single.to_str().unwrap(),
+        archive.to_str().unwrap(),
+        "-o",
+        "out",
+    ]);
+    let names: Vec<String> = collect_games(&args)
+        .unwrap()
+        .into_iter()
+        .map(|game| game.name)
+        .collect();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(names, ["single", "archive-1", "archive-2"]);
+}
+
+#[test]
+fn formats_mirror_the_query_options() {
+    let args = Args::parse_from([
+        "ms-render",
+        "--data",
+        LOST_V2,
+        "--format",
+        "emoji",
+        "-o",
+        "-",
+    ]);
+    let game = &collect_games(&args).unwrap()[0];
+
+    let (text, extension) = encode(game, args.format, &args).unwrap();
+    assert_eq!(extension, "txt");
+    assert_eq!(String::from_utf8(text).unwrap().lines().count(), 2);
+
+    let (json, extension) = encode(game, Format::Json, &args).unwrap();
+    assert_eq!(extension, "json");
+    assert!(String::from_utf8(json)
+        .unwrap()
+        .contains("\"donePercentage\": 85"));
+}
+
+#[test]
+fn broken_lines_are_counted_while_the_rest_renders() {
+    let dir = std::env::temp_dir().join(format!("ms-render-broken-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let archive = dir.join("arch.txt");
+    std::fs::write(
+        &archive,
+        format!("{LOST_V2}\n2=garbage+x+y+z\n2=4x2,10+30+101;302+001X\n{LOST_V2}\n"),
+    )
+    .unwrap();
+    let output = dir.join("out");
+
+    let args = Args::parse_from([
+        "ms-render",
+        "--file",
+        archive.to_str().unwrap(),
+        "--format",
+        "txt",
+        "-o",
+        output.to_str().unwrap(),
+    ]);
+    let result = run(args);
+    let mut rendered: Vec<String> = std::fs::read_dir(&output)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+        .collect();
+    rendered.sort();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(
+        result,
+        Err("2 of 4 games could not be rendered".to_string())
+    );
+    assert_eq!(rendered, ["arch-1.txt", "arch-4.txt"]);
+}
+
+/// A dozen opens whose times add up beyond an i64, a negative v1 time and a game lasting months
+fn malformed_times() -> [String; 3] {
+    [
+        format!("2=2x1,10++{}+", ["00zzzzzzzzzz"; 12].join(";")),
+        "1=2x1,10+0|1+0|0:-5+".to_string(),
+        "2=2x1,10000+10+00zzzz+".to_string(),
+    ]
+}
+
+#[test]
+fn malformed_times_are_parse_errors() {
+    for data in malformed_times() {
+        assert!(
+            matches!(parse_game_data(&data), Err(MinesweeperError::DataParse)),
+            "{data} should not parse"
+        );
+    }
+}
+
+#[test]
+fn malformed_times_fail_in_every_format() {
+    for data in malformed_times() {
+        for format in ["png", "gif", "avi", "svg", "txt", "emoji", "json"] {
+            let args =
+                Args::parse_from(["ms-render", "--data", &data, "--format", format, "-o", "-"]);
+            let game = &collect_games(&args).unwrap()[0];
+
+            assert!(
+                encode(game, args.format, &args).is_err(),
+                "{data} as {format}"
+            );
+        }
+    }
+}
+
+#[test]
+fn hours_long_games_render_in_every_format() {
+    for format in ["png", "gif", "avi", "svg", "json"] {
+        for progress in ["ticks", "time", "timeline"] {
+            let args = Args::parse_from([
+                "ms-render",
+                "--data",
+                "2=3x1,1+20+00zzzz;10zzzz+00zzzzP",
+                "--format",
+                format,
+                "--progress",
+                progress,
+                "-o",
+                "-",
+            ]);
+            let game = &collect_games(&args).unwrap()[0];
+
+            assert!(
+                encode(game, args.format, &args).is_ok(),
+                "{format} {progress}"
+            );
+        }
+    }
+}
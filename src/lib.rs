@@ -1,17 +1,31 @@
 use std::collections::HashMap;
 
+use futures_util::stream;
 use worker::*;
 
-use crate::minesweeper::error::MinesweeperError;
-use crate::minesweeper::parsers;
-use crate::minesweeper::parsers::parser::{Iparser, ParsedData};
-use crate::minesweeper::provider::greev::greev_provider::GreevProvider;
-use crate::minesweeper::provider::mcplayhd::mcplay_provider::McPlayHdProvider;
-use crate::minesweeper::provider::provider::EnumProviders::{Greev, McPlayHd};
-use crate::minesweeper::provider::provider::{ApiData, EnumProviders, Provider};
-use crate::minesweeper::renderer::Renderer;
+use minesweeper::analysis::anticheat;
+use minesweeper::analysis::classifier::classify;
+use minesweeper::analysis::overlay::OverlayType;
+use minesweeper::analysis::probability::probability_map;
+use minesweeper::analysis::replay::merge_moves;
+use minesweeper::compare::{Comparison, MAX_PLAYERS};
+use minesweeper::error::MinesweeperError;
+use minesweeper::output::{render, RenderOptions, RenderOutput};
+use minesweeper::parsers::parser::{parse_game_data, Generator, ParsedData};
+use minesweeper::profile::fetch_profile;
+use minesweeper::provider::config::{ConfigKey, ConfigKind, ConfigValues, ProviderConfig};
+use minesweeper::provider::custom::custom_provider::CustomConfig;
+use minesweeper::provider::game_id::GameIdFormat;
+use minesweeper::provider::greev::greev_provider::GreevProvider;
+use minesweeper::provider::mcplayhd::mcplay_provider::McPlayHdProvider;
+use minesweeper::provider::provider::EnumProviders::{Custom, Greev, McPlayHd};
+use minesweeper::provider::provider::{ApiData, EnumProviders, Provider};
+use minesweeper::renderer::{GifStream, ProgressStyle, RenderType, Renderer};
+use minesweeper::stats::{mismatches, GameStats, Mismatch, ReplayStats};
 
-mod minesweeper;
+use crate::worker_client::WorkerClient;
+
+mod worker_client;
 
 #[event(fetch)]
 async fn main(req: Request, env: Env, _ctx: Context) -> Result<Response> {
@@ -19,121 +33,520 @@ async fn main(req: Request, env: Env, _ctx: Context) -> Result<Response> {
 
     router
         .get_async("/render/:provider/:gameid", |request, context| async move {
-            let Some(game_id) = context.param("gameid") else {
-                return Response::error("GameId Missing", 400);
-            };
-
-            let Some(provider) = context.param("provider") else {
-                return Response::error("Provider Missing", 400);
-            };
-
             let hash_query: HashMap<_, _> = request.url()?.query_pairs().into_owned().collect();
             let gif = hash_query
                 .get("gif")
                 .map(|x| x.parse::<bool>().unwrap_or(false))
                 .unwrap_or(false);
+            let render_type = match hash_query.get("format").map(|x| x.parse::<RenderType>()) {
+                Some(Ok(render_type)) => render_type,
+                Some(Err(err)) => return Response::error(err, 400),
+                None if gif => RenderType::Gif,
+                None => RenderType::Image,
+            };
+            let overlay = match hash_query.get("overlay").map(|x| x.parse::<OverlayType>()) {
+                Some(Ok(overlay)) => Some(overlay),
+                Some(Err(err)) => return Response::error(err, 400),
+                None => None,
+            };
+            let progress_style = match hash_query.get("progress").map(|x| x.parse()) {
+                Some(Ok(progress_style)) => progress_style,
+                Some(Err(err)) => return Response::error(err, 400),
+                None => ProgressStyle::default(),
+            };
 
-            let possible_providers: Vec<EnumProviders> =
-                vec![Greev(GreevProvider), McPlayHd(McPlayHdProvider)];
-
-            let optional_provider = possible_providers.iter().find(|x| match x {
-                Greev(x) => x.id() == provider.as_str(),
-                McPlayHd(x) => x.id() == provider.as_str(),
-            });
+            let api_data = match fetch_api_data(&context).await {
+                Ok(api_data) => api_data,
+                Err(err) => return err.into_response(),
+            };
 
-            let Some(provider) = optional_provider else {
-                return Response::error("Unknown Provider", 400);
+            let options = RenderOptions {
+                render_type,
+                overlay,
+                progress_style,
+            };
+            let (output, mismatches) = match get_image_data(&api_data, options) {
+                Ok(image_data) => image_data,
+                Err(err) => {
+                    return RouteError::upstream(
+                        format!("Unable to fetch image data because of {}", err),
+                        &err,
+                    )
+                    .into_response()
+                }
             };
 
-            let mut options: HashMap<String, String> = HashMap::new();
-            let mcplay_api_key = context.secret("MCPLAYHD_API_KEY");
-            if let Ok(api_key) = mcplay_api_key {
-                options.insert("api_key".to_string(), api_key.to_string());
-            }
+            let content_type = output.content_type();
+            let mut response = match output {
+                RenderOutput::Image(data) => Response::from_body(ResponseBody::Body(data)),
+                RenderOutput::Gif(gif_stream) => stream_response(*gif_stream),
+                RenderOutput::Video(avi_stream) => stream_response(avi_stream),
+                RenderOutput::Svg(text) | RenderOutput::Text(text) => Response::ok(text),
+            }?;
+            response.headers_mut().set("Content-Type", content_type)?;
+            set_mismatch_header(&mut response, &mismatches)?;
+            Ok(response)
+        })
+        .get_async(
+            "/compare/:provider/:gameA/:gameB",
+            |request, context| async move {
+                let progress_style = match grid_progress_style(&request) {
+                    Ok(progress_style) => progress_style,
+                    Err(err) => return Response::error(err, 400),
+                };
+
+                let game_ids: Vec<String> = ["gameA", "gameB"]
+                    .iter()
+                    .filter_map(|param| context.param(param).cloned())
+                    .collect();
+                if game_ids.len() != 2 {
+                    return Response::error("GameId Missing", 400);
+                }
 
-            let result_api_data = provider.fetch_data(game_id, Some(options)).await;
+                let games = match fetch_games(&context, &game_ids).await {
+                    Ok(games) => games,
+                    Err(err) => return err.into_response(),
+                };
+                if !same_board(&games[0].1, &games[1].1) {
+                    return Response::error("The games were not played on the same board", 400);
+                }
+
+                grid_response(
+                    ["A", "B"]
+                        .iter()
+                        .zip(games)
+                        .map(|(label, (name, game))| (format!("{}: {}", label, name), game))
+                        .collect(),
+                    progress_style,
+                )
+            },
+        )
+        .get_async("/race/:provider/:gameids", |request, context| async move {
+            let progress_style = match grid_progress_style(&request) {
+                Ok(progress_style) => progress_style,
+                Err(err) => return Response::error(err, 400),
+            };
 
-            if let Err(err) = result_api_data {
+            let Some(game_ids) = context.param("gameids") else {
+                return Response::error("GameId Missing", 400);
+            };
+            let game_ids: Vec<String> = game_ids
+                .split(',')
+                .map(|game_id| game_id.trim().to_string())
+                .filter(|game_id| !game_id.is_empty())
+                .collect();
+            if !(2..=MAX_PLAYERS).contains(&game_ids.len()) {
                 return Response::error(
-                    format!("Unable to fetch game data because of {}", err),
-                    500,
+                    format!("A race needs between 2 and {} games", MAX_PLAYERS),
+                    400,
                 );
             }
 
-            let api_data = result_api_data.unwrap();
+            let games = match fetch_games(&context, &game_ids).await {
+                Ok(games) => games,
+                Err(err) => return err.into_response(),
+            };
 
-            let image_data_result = get_image_data(&api_data, &gif).await;
+            grid_response(
+                games
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, (name, game))| (format!("{}: {}", index + 1, name), game))
+                    .collect(),
+                progress_style,
+            )
+        })
+        .get_async("/stats/:provider/:gameid", |_request, context| async move {
+            let (api_data, parsed_data) = match fetch_parsed_data(&context).await {
+                Ok(data) => data,
+                Err(err) => return err.into_response(),
+            };
 
-            if image_data_result.is_err() {
-                return Response::error("Unable to fetch image data", 500);
-            }
+            let stats = GameStats::new(&api_data, &parsed_data);
+            let mut response = Response::from_json(&stats)?;
+            set_mismatch_header(&mut response, &stats.mismatches)?;
+            Ok(response)
+        })
+        .get_async("/player/:provider/:uuid", |request, context| async move {
+            let hash_query: HashMap<_, _> = request.url()?.query_pairs().into_owned().collect();
+            let card = match hash_query.get("format").map(String::as_str) {
+                Some("png" | "image") => true,
+                Some("json") | None => false,
+                Some(_) => return Response::error("Unknown format", 400),
+            };
 
-            if let Some(data) = image_data_result.unwrap() {
-                return Response::from_body(ResponseBody::Body(data));
+            let Some(uuid) = context.param("uuid") else {
+                return Response::error("UUID Missing", 400);
+            };
+            // The uuid ends up in the url of the provider, so nothing else may get through
+            let Some(uuid) = GameIdFormat::Uuid.normalize(uuid) else {
+                return Response::error("Invalid UUID", 400);
+            };
+            let provider = match find_provider(&context) {
+                Ok(provider) => provider,
+                Err(err) => return err.into_response(),
+            };
+
+            let config = provider_config(&context, &provider);
+            let profile = match fetch_profile(&provider, &WorkerClient, &uuid, &config).await {
+                Ok(profile) => profile,
+                Err(err) => {
+                    return RouteError::upstream(
+                        format!(
+                            "Unable to fetch the player from {} because of {}",
+                            provider.name(),
+                            err
+                        ),
+                        &err,
+                    )
+                    .into_response()
+                }
+            };
+
+            if !card {
+                return Response::from_json(&profile);
             }
 
-            Response::error("Unable to fetch image data", 500)
+            match profile.render_card() {
+                Ok(png) => {
+                    let mut response = Response::from_bytes(png)?;
+                    response.headers_mut().set("Content-Type", "image/png")?;
+                    Ok(response)
+                }
+                Err(err) => Response::error(err.to_string(), 500),
+            }
         })
+        .get_async(
+            "/analysis/:provider/:gameid",
+            |_request, context| async move {
+                let parsed_data = match fetch_parsed_data(&context).await {
+                    Ok((_, parsed_data)) => parsed_data,
+                    Err(err) => return err.into_response(),
+                };
+
+                Response::from_json(&anticheat::analyse(&parsed_data))
+            },
+        )
+        .get_async(
+            "/solver/:provider/:gameid",
+            |_request, context| async move {
+                let parsed_data = match fetch_parsed_data(&context).await {
+                    Ok((_, parsed_data)) => parsed_data,
+                    Err(err) => return err.into_response(),
+                };
+
+                let moves = merge_moves(
+                    &parsed_data.open_data,
+                    &parsed_data.flag_data,
+                    &parsed_data.chord_data,
+                );
+
+                Response::from_json(&classify(&parsed_data.game_board, &moves))
+            },
+        )
+        .get_async(
+            "/probability/:provider/:gameid",
+            |request, context| async move {
+                let hash_query: HashMap<_, _> = request.url()?.query_pairs().into_owned().collect();
+                let tick = match hash_query.get("tick").map(|x| x.parse::<i64>()) {
+                    Some(Ok(tick)) => Some(tick),
+                    Some(Err(_)) => return Response::error("Invalid tick", 400),
+                    None => None,
+                };
+
+                let parsed_data = match fetch_parsed_data(&context).await {
+                    Ok((_, parsed_data)) => parsed_data,
+                    Err(err) => return err.into_response(),
+                };
+
+                let moves = merge_moves(
+                    &parsed_data.open_data,
+                    &parsed_data.flag_data,
+                    &parsed_data.chord_data,
+                );
+
+                Response::from_json(&probability_map(&parsed_data.game_board, &moves, tick))
+            },
+        )
         .run(req, env)
         .await
 }
 
-async fn get_image_data(
-    api_data: &ApiData,
-    mut gif: &bool,
-) -> std::result::Result<Option<Vec<u8>>, MinesweeperError> {
-    if let Some(game_data) = &api_data.game_data {
-        let option = game_data.split_once('=').expect("Unable to get Version");
+/// Resolves the provider from the route and fetches the game from it.
+/// On failure the error message and status code for the response are returned.
+async fn fetch_api_data(context: &RouteContext<()>) -> std::result::Result<ApiData, RouteError> {
+    let Some(game_id) = context.param("gameid") else {
+        return Err(RouteError::new("GameId Missing", 400));
+    };
 
-        let possible_parsers: Vec<&dyn Iparser> = vec![
-            &parsers::v1::parser::ParserV1,
-            &parsers::v2::parser::ParserV2,
-        ];
+    fetch_game(context, game_id).await
+}
 
-        let option_found_parser = possible_parsers
-            .iter()
-            .find(|p| p.supported_versions().contains(&option.0));
+/// Fetches the game with the given id from the provider of the route.
+async fn fetch_game(
+    context: &RouteContext<()>,
+    game_id: &str,
+) -> std::result::Result<ApiData, RouteError> {
+    let provider = find_provider(context)?;
+
+    provider
+        .fetch_data(&WorkerClient, game_id, &provider_config(context, &provider))
+        .await
+        .map_err(|err| {
+            RouteError::upstream(
+                format!(
+                    "Unable to fetch game data from {} because of {}",
+                    provider.name(),
+                    err
+                ),
+                &err,
+            )
+        })
+}
+
+/// Resolves the provider named in the route, custom providers come from their settings.
+fn find_provider(context: &RouteContext<()>) -> std::result::Result<EnumProviders, RouteError> {
+    let Some(provider) = context.param("provider") else {
+        return Err(RouteError::new("Provider Missing", 400));
+    };
+
+    let mut possible_providers: Vec<EnumProviders> =
+        vec![Greev(GreevProvider), McPlayHd(McPlayHdProvider)];
+    match CustomConfig::from_values(&config_values(context, CustomConfig::KEYS)) {
+        Ok(config) => possible_providers.extend(config.providers().map(Custom)),
+        Err(MinesweeperError::MissingConfig(_)) => {}
+        Err(err) => return Err(RouteError::upstream(err.to_string(), &err)),
+    }
 
-        if option_found_parser.is_none() {
-            return Err(MinesweeperError::UnsupportedVersion);
+    let optional_provider = possible_providers.into_iter().find(|x| match x {
+        Greev(x) => x.id() == provider.as_str(),
+        McPlayHd(x) => x.id() == provider.as_str(),
+        Custom(x) => x.id() == provider.as_str(),
+    });
+
+    optional_provider.ok_or(RouteError::new("Unknown Provider", 400))
+}
+
+/// Resolves the secrets and variables the provider declares, missing ones are left out.
+fn provider_config(context: &RouteContext<()>, provider: &EnumProviders) -> ConfigValues {
+    config_values(context, provider.config_keys())
+}
+
+fn config_values(context: &RouteContext<()>, keys: &[ConfigKey]) -> ConfigValues {
+    let mut values = ConfigValues::default();
+    for key in keys {
+        let value = match key.kind {
+            ConfigKind::Secret => context.secret(key.name).map(|secret| secret.to_string()),
+            ConfigKind::Var => context.var(key.name).map(|var| var.to_string()),
+        };
+        if let Ok(value) = value {
+            values.insert(key.name, value);
         }
+    }
 
-        let parser = option_found_parser.unwrap();
+    values
+}
 
-        let split: Vec<&str> = option.1.split('+').collect();
+/// Message and status code of the response of a failed route.
+struct RouteError {
+    message: String,
+    status: u16,
+    /// Seconds until a rate limited provider accepts requests again
+    retry_after: Option<u64>,
+}
 
-        let metadata = parser.parse_meta_data(split[0].trim());
+impl RouteError {
+    fn new(message: impl Into<String>, status: u16) -> Self {
+        RouteError {
+            message: message.into(),
+            status,
+            retry_after: None,
+        }
+    }
 
-        let game_data = ParsedData {
-            game_board: parser.parse_mine_data(split[1].trim(), &metadata),
-            open_data: parser.parse_open_data(split[2].trim()),
-            flag_data: parser.parse_flag_data(split[3].trim()),
-            metadata,
+    /// Error of fetching, parsing or rendering a game, passing on when to retry
+    fn upstream(message: String, err: &MinesweeperError) -> Self {
+        let retry_after = match err {
+            MinesweeperError::RateLimited { retry_after } => *retry_after,
+            _ => None,
         };
 
-        //If the field is too large overwrite the gif value to not render a gif
-        if game_data.metadata.x_size > 32 || game_data.metadata.y_size > 32 {
-            gif = &false
+        RouteError {
+            message,
+            status: upstream_status(err),
+            retry_after,
         }
+    }
+
+    fn into_response(self) -> Result<Response> {
+        let mut response = Response::error(self.message, self.status)?;
+        if let Some(retry_after) = self.retry_after {
+            response
+                .headers_mut()
+                .set("Retry-After", &retry_after.to_string())?;
+        }
+        Ok(response)
+    }
+}
+
+/// Status code of the response when fetching, parsing or rendering a game failed.
+fn upstream_status(err: &MinesweeperError) -> u16 {
+    match err {
+        MinesweeperError::InvalidGameId(_)
+        | MinesweeperError::DataParse
+        | MinesweeperError::UnsupportedVersion
+        | MinesweeperError::UnsupportedBoardSize => 400,
+        MinesweeperError::UpstreamNotFound | MinesweeperError::GameDataNotFound => 404,
+        MinesweeperError::RateLimited { .. } => 429,
+        MinesweeperError::UpstreamTimeout => 504,
+        MinesweeperError::UpstreamAuth
+        | MinesweeperError::UpstreamServer(_)
+        | MinesweeperError::UpstreamStatus(_)
+        | MinesweeperError::UpstreamRequest
+        | MinesweeperError::ApiDataParse => 502,
+        MinesweeperError::Unsupported => 501,
+        // The Worker is set up wrong, which is neither the fault of the client nor the provider
+        MinesweeperError::MissingConfig(_) | MinesweeperError::InvalidConfig(_) => 500,
+        _ => 500,
+    }
+}
+
+/// Fetches the game like [fetch_api_data] and parses the contained game data.
+async fn fetch_parsed_data(
+    context: &RouteContext<()>,
+) -> std::result::Result<(ApiData, ParsedData), RouteError> {
+    let api_data = fetch_api_data(context).await?;
+    let parsed_data = parse_api_data(&api_data)?;
+
+    Ok((api_data, parsed_data))
+}
+
+/// Parses the game data contained in a fetched game.
+fn parse_api_data(api_data: &ApiData) -> std::result::Result<ParsedData, RouteError> {
+    let Some(game_data) = &api_data.game_data else {
+        return Err(RouteError::new("Game data not found", 404));
+    };
+
+    Ok(parse_game_data(game_data)
+        .map_err(|err| {
+            RouteError::upstream(
+                format!("Unable to parse game data because of {}", err),
+                &err,
+            )
+        })?
+        .with_generator(api_data.generator.as_deref().map(Generator::from)))
+}
 
-        let mut renderer = Renderer::new(
-            game_data.metadata,
-            game_data.game_board,
-            game_data.open_data,
-            game_data.flag_data,
-            gif,
-        );
-
-        Ok(Some(if *gif {
-            renderer
-                .render_gif()
-                .map_err(|_| MinesweeperError::ImageRender)?
-        } else {
-            renderer
-                .render_jpeg()
-                .map_err(|_| MinesweeperError::ImageRender)?
-        }))
-    } else {
-        Ok(None)
+/// Streams the chunks out while they are encoded, so a gif only keeps one frame in memory.
+fn stream_response(
+    chunks: impl Iterator<Item = std::result::Result<Vec<u8>, MinesweeperError>> + 'static,
+) -> Result<Response> {
+    Response::from_stream(stream::iter(
+        chunks.map(|chunk| chunk.map_err(|err| Error::RustError(err.to_string()))),
+    ))
+}
+
+/// Fetches and parses every game from the provider of the route.
+/// Every game is paired with the name of its player, or its id if the provider has no names.
+async fn fetch_games(
+    context: &RouteContext<()>,
+    game_ids: &[String],
+) -> std::result::Result<Vec<(String, ParsedData)>, RouteError> {
+    // Every id is checked up front, so a bad one is rejected before any game is fetched
+    let provider = find_provider(context)?;
+    let config = provider_config(context, &provider);
+    for game_id in game_ids {
+        provider
+            .normalize_game_id(game_id, &config)
+            .map_err(|err| RouteError::upstream(err.to_string(), &err))?;
+    }
+
+    let mut games = Vec::with_capacity(game_ids.len());
+    for game_id in game_ids {
+        let api_data = fetch_game(context, game_id).await?;
+        let name = api_data.player_name().unwrap_or(game_id).to_string();
+        games.push((name, parse_api_data(&api_data)?));
     }
+
+    Ok(games)
+}
+
+/// Progress style of the boards in a grid, showing the opened cells unless requested otherwise.
+fn grid_progress_style(request: &Request) -> std::result::Result<ProgressStyle, String> {
+    let hash_query: HashMap<_, _> = request
+        .url()
+        .map_err(|err| err.to_string())?
+        .query_pairs()
+        .into_owned()
+        .collect();
+
+    hash_query
+        .get("progress")
+        .map_or(Ok(ProgressStyle::Cells), |x| x.parse())
+}
+
+/// Replays the labelled games in a grid on a shared clock.
+fn grid_response(
+    games: Vec<(String, ParsedData)>,
+    progress_style: ProgressStyle,
+) -> Result<Response> {
+    let max_dimension = Comparison::max_board_dimension(games.len());
+
+    let frames = games
+        .into_iter()
+        .map(|(label, parsed_data)| {
+            let renderer = Renderer::from_parsed(parsed_data, true)
+                .with_max_dimension(max_dimension)
+                .with_progress_style(progress_style);
+            (label, renderer.into_frames())
+        })
+        .collect();
+
+    match Comparison::new(frames).and_then(GifStream::new) {
+        Ok(gif_stream) => stream_response(gif_stream),
+        Err(err) => RouteError::upstream(
+            format!("Unable to fetch image data because of {}", err),
+            &err,
+        )
+        .into_response(),
+    }
+}
+
+/// Whether both games have the same size and mines.
+fn same_board(a: &ParsedData, b: &ParsedData) -> bool {
+    a.metadata.x_size == b.metadata.x_size
+        && a.metadata.y_size == b.metadata.y_size
+        && a.game_board
+            .fields
+            .iter()
+            .flatten()
+            .zip(b.game_board.fields.iter().flatten())
+            .all(|(a, b)| a.mine == b.mine)
+}
+
+fn get_image_data(
+    api_data: &ApiData,
+    options: RenderOptions,
+) -> std::result::Result<(RenderOutput, Vec<Mismatch>), MinesweeperError> {
+    let game_data = api_data
+        .game_data
+        .as_ref()
+        .ok_or(MinesweeperError::GameDataNotFound)?;
+    let parsed_data = parse_game_data(game_data)?
+        .with_generator(api_data.generator.as_deref().map(Generator::from));
+    let mismatches = mismatches(api_data, &ReplayStats::new(&parsed_data));
+
+    Ok((render(parsed_data, options)?, mismatches))
+}
+
+/// Lists the fields the provider and the replay disagree on, so clients notice broken games.
+fn set_mismatch_header(response: &mut Response, mismatches: &[Mismatch]) -> Result<()> {
+    if mismatches.is_empty() {
+        return Ok(());
+    }
+
+    let fields: Vec<&str> = mismatches.iter().map(|mismatch| mismatch.field).collect();
+    response
+        .headers_mut()
+        .set("X-Replay-Mismatches", &fields.join(","))
 }
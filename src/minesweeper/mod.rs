@@ -1,8 +0,0 @@
-mod base36;
-mod base62;
-pub mod error;
-pub mod minesweeper_logic;
-pub mod parsers;
-pub(crate) mod provider;
-pub mod renderer;
-mod textures;
@@ -1,3 +0,0 @@
-pub(crate) mod greev;
-pub(crate) mod mcplayhd;
-pub mod provider;
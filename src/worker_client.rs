@@ -0,0 +1,62 @@
+use std::pin::pin;
+use std::time::Duration;
+
+use futures_util::future::{select, Either};
+use worker::{
+    AbortController, Delay, Fetch, Headers, Method, Request, RequestInit, RequestRedirect,
+};
+
+use minesweeper::error::MinesweeperError;
+use minesweeper::provider::http::{HttpClient, HttpRequest, HttpResponse};
+
+/// Sends the requests of the providers with the fetch api of the Worker runtime.
+pub struct WorkerClient;
+
+impl HttpClient for WorkerClient {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse, MinesweeperError> {
+        let mut headers = Headers::new();
+        for (name, value) in &request.headers {
+            headers
+                .set(name, value)
+                .map_err(|_| MinesweeperError::UpstreamRequest)?;
+        }
+
+        let mut request_init = RequestInit::new();
+        request_init
+            .with_method(Method::Get)
+            .with_headers(headers)
+            .with_redirect(RequestRedirect::Follow);
+
+        let fetch = Fetch::Request(
+            Request::new_with_init(&request.url, &request_init)
+                .map_err(|_| MinesweeperError::UpstreamRequest)?,
+        );
+        let controller = AbortController::default();
+        let signal = controller.signal();
+
+        let send = pin!(fetch.send_with_signal(&signal));
+        let timeout = pin!(Delay::from(request.timeout));
+        let mut response = match select(send, timeout).await {
+            Either::Left((response, _)) => {
+                response.map_err(|_| MinesweeperError::UpstreamRequest)?
+            }
+            Either::Right(_) => {
+                controller.abort();
+                return Err(MinesweeperError::UpstreamTimeout);
+            }
+        };
+
+        Ok(HttpResponse {
+            status: response.status_code(),
+            headers: response.headers().entries().collect(),
+            body: response
+                .bytes()
+                .await
+                .map_err(|_| MinesweeperError::UpstreamRequest)?,
+        })
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        Delay::from(duration).await;
+    }
+}